@@ -14,81 +14,190 @@ pub fn generate_all_adjective_conjugations(
             "" => adjective_kanji_form,
             _ => adjective_reading_form,
         };
-        Some(vec![
-            ConjugatedWord {
-                kanji_form: negative(adjective_kanji_form, category),
-                kana_form: negative(adjective_kana_form, category),
-                grammatical_forms: vec![GrammaticalForm::Negative],
-            },
-            ConjugatedWord {
-                kanji_form: past(adjective_kanji_form, category),
-                kana_form: past(adjective_kana_form, category),
-                grammatical_forms: vec![GrammaticalForm::Past],
-            },
-            ConjugatedWord {
-                kanji_form: te_form(adjective_kanji_form, category),
-                kana_form: te_form(adjective_kana_form, category),
-                grammatical_forms: vec![GrammaticalForm::Negative],
-            },
-            ConjugatedWord {
-                kanji_form: adverbial(adjective_kanji_form, category),
-                kana_form: adverbial(adjective_kana_form, category),
-                grammatical_forms: vec![GrammaticalForm::Adverbial],
-            },
-            ConjugatedWord {
-                kanji_form: past_negative(adjective_kanji_form, category),
-                kana_form: past_negative(adjective_kana_form, category),
-                grammatical_forms: vec![GrammaticalForm::Past, GrammaticalForm::Negative],
-            },
-        ])
+        let kanji_stem = stem(adjective_kanji_form, category)?;
+        let kana_stem = stem(adjective_kana_form, category)?;
+        let mut conjugations: Vec<ConjugatedWord> = I_ADJECTIVE_ENDINGS
+            .iter()
+            .map(|(ending, grammatical_forms)| {
+                ConjugatedWord::new(
+                    [&kanji_stem, *ending].concat(),
+                    [&kana_stem, *ending].concat(),
+                    grammatical_forms.to_vec(),
+                )
+            })
+            .collect();
+        conjugations.push(ConjugatedWord::new(
+            [adjective_kanji_form, "です"].concat(),
+            [adjective_kana_form, "です"].concat(),
+            vec![GrammaticalForm::Polite],
+        ));
+        conjugations.push(ConjugatedWord::new(
+            appearance(adjective_kanji_form, &kanji_stem, category),
+            appearance(adjective_kana_form, &kana_stem, category),
+            vec![GrammaticalForm::Appearance],
+        ));
+        Some(conjugations)
     } else {
         None
     }
 }
 
-fn negative(adjective: &str, category: &str) -> String {
-    let (ending, suffix_len) = match adjective.chars().rev().nth(1).unwrap() {
-        'い' if category == "adj-ix" => ("よくない", 2),
-        _ => ("くない", 1),
-    };
-    let prefix_len = adjective.len() - suffix_len * 3;
-    [&adjective[..prefix_len], ending].concat()
+pub fn generate_all_na_adjective_conjugations(
+    adjective_kanji_form: &str,
+    adjective_reading_form: &str,
+    tags: Vec<&String>,
+) -> Option<Vec<ConjugatedWord>> {
+    if !tags.iter().any(|&tag| NA_ADJECTIVE_TAGS.contains(tag)) || adjective_kanji_form.is_empty() {
+        None
+    } else {
+        let adjective_kana_form = match adjective_reading_form {
+            "" => adjective_kanji_form,
+            _ => adjective_reading_form,
+        };
+        Some(
+            NA_ADJECTIVE_ENDINGS
+                .iter()
+                .map(|(ending, grammatical_forms)| {
+                    ConjugatedWord::new(
+                        [adjective_kanji_form, ending].concat(),
+                        [adjective_kana_form, ending].concat(),
+                        grammatical_forms.to_vec(),
+                    )
+                })
+                .collect(),
+        )
+    }
 }
 
-fn past(adjective: &str, category: &str) -> String {
-    let (ending, suffix_len) = match adjective.chars().rev().nth(1).unwrap() {
-        'い' if category == "adj-ix" => ("よかった", 2),
-        _ => ("かった", 1),
-    };
-    let prefix_len = adjective.len() - suffix_len * 3;
-    [&adjective[..prefix_len], ending].concat()
+/// Drops the final い, いい (adj-ix) conjugates from the stem よ. `None` for
+/// words tagged as adjectives that don't end in い.
+fn stem(adjective: &str, category: &str) -> Option<String> {
+    match adjective.strip_suffix("いい") {
+        Some(prefix) if category == "adj-ix" => Some([prefix, "よ"].concat()),
+        _ => adjective.strip_suffix('い').map(String::from),
+    }
 }
 
-fn te_form(adjective: &str, category: &str) -> String {
-    let (ending, suffix_len) = match adjective.chars().rev().nth(1).unwrap() {
-        'い' if category == "adj-ix" => ("よくて", 2),
-        _ => ("くて", 1),
-    };
-    let prefix_len = adjective.len() - suffix_len * 3;
-    [&adjective[..prefix_len], ending].concat()
+fn appearance(adjective: &str, stem: &str, category: &str) -> String {
+    // ない and よい keep their stem alive with さ
+    match adjective {
+        "ない" | "無い" => [stem, "さそう"].concat(),
+        _ if adjective.ends_with("よい") || adjective.ends_with("良い") => {
+            [stem, "さそう"].concat()
+        }
+        _ if category == "adj-ix" => [stem, "さそう"].concat(),
+        _ => [stem, "そう"].concat(),
+    }
 }
 
-fn adverbial(adjective: &str, category: &str) -> String {
-    let (ending, suffix_len) = match adjective.chars().rev().nth(1).unwrap() {
-        'い' if category == "adj-ix" => ("よく", 2),
-        _ => ("く", 1),
-    };
-    let prefix_len = adjective.len() - suffix_len * 3;
-    [&adjective[..prefix_len], ending].concat()
-}
+const I_ADJECTIVE_ENDINGS: [(&str, &[GrammaticalForm]); 17] = [
+    ("くない", &[GrammaticalForm::Negative]),
+    ("かった", &[GrammaticalForm::Past]),
+    (
+        "くなかった",
+        &[GrammaticalForm::Negative, GrammaticalForm::Past],
+    ),
+    (
+        "かったです",
+        &[GrammaticalForm::Polite, GrammaticalForm::Past],
+    ),
+    (
+        "くありません",
+        &[GrammaticalForm::Polite, GrammaticalForm::Negative],
+    ),
+    (
+        "くないです",
+        &[GrammaticalForm::Polite, GrammaticalForm::Negative],
+    ),
+    (
+        "くありませんでした",
+        &[
+            GrammaticalForm::Polite,
+            GrammaticalForm::Negative,
+            GrammaticalForm::Past,
+        ],
+    ),
+    ("くて", &[GrammaticalForm::TeForm]),
+    (
+        "くなくて",
+        &[GrammaticalForm::Negative, GrammaticalForm::TeForm],
+    ),
+    ("く", &[GrammaticalForm::Adverbial]),
+    ("ければ", &[GrammaticalForm::ProvisionalConditional]),
+    (
+        "くなければ",
+        &[
+            GrammaticalForm::Negative,
+            GrammaticalForm::ProvisionalConditional,
+        ],
+    ),
+    ("かったら", &[GrammaticalForm::Conditional]),
+    (
+        "くなかったら",
+        &[GrammaticalForm::Negative, GrammaticalForm::Conditional],
+    ),
+    ("かろう", &[GrammaticalForm::Volitional]),
+    ("さ", &[GrammaticalForm::Nominal]),
+    ("すぎる", &[GrammaticalForm::Excessive]),
+];
 
-fn past_negative(adjective: &str, category: &str) -> String {
-    let (ending, suffix_len) = match adjective.chars().rev().nth(1).unwrap() {
-        'い' if category == "adj-ix" => ("よくなかった", 2),
-        _ => ("くなかった", 1),
-    };
-    let prefix_len = adjective.len() - suffix_len * 3;
-    [&adjective[..prefix_len], ending].concat()
+const NA_ADJECTIVE_ENDINGS: [(&str, &[GrammaticalForm]); 19] = [
+    ("だ", &[GrammaticalForm::Predicative]),
+    ("です", &[GrammaticalForm::Polite]),
+    ("じゃない", &[GrammaticalForm::Negative]),
+    ("ではない", &[GrammaticalForm::Negative]),
+    ("だった", &[GrammaticalForm::Past]),
+    ("でした", &[GrammaticalForm::Polite, GrammaticalForm::Past]),
+    (
+        "じゃなかった",
+        &[GrammaticalForm::Negative, GrammaticalForm::Past],
+    ),
+    (
+        "ではなかった",
+        &[GrammaticalForm::Negative, GrammaticalForm::Past],
+    ),
+    (
+        "じゃありません",
+        &[GrammaticalForm::Polite, GrammaticalForm::Negative],
+    ),
+    (
+        "ではありません",
+        &[GrammaticalForm::Polite, GrammaticalForm::Negative],
+    ),
+    (
+        "じゃありませんでした",
+        &[
+            GrammaticalForm::Polite,
+            GrammaticalForm::Negative,
+            GrammaticalForm::Past,
+        ],
+    ),
+    (
+        "ではありませんでした",
+        &[
+            GrammaticalForm::Polite,
+            GrammaticalForm::Negative,
+            GrammaticalForm::Past,
+        ],
+    ),
+    ("で", &[GrammaticalForm::TeForm]),
+    (
+        "じゃなくて",
+        &[GrammaticalForm::Negative, GrammaticalForm::TeForm],
+    ),
+    (
+        "ではなくて",
+        &[GrammaticalForm::Negative, GrammaticalForm::TeForm],
+    ),
+    ("に", &[GrammaticalForm::Adverbial]),
+    ("な", &[GrammaticalForm::Attributive]),
+    ("なら", &[GrammaticalForm::ProvisionalConditional]),
+    ("だったら", &[GrammaticalForm::Conditional]),
+];
+
+lazy_static! {
+    static ref NA_ADJECTIVE_TAGS: HashSet<String> =
+        vec![String::from("adj-na")].into_iter().collect();
 }
 
 lazy_static! {
@@ -100,3 +209,234 @@ lazy_static! {
     .into_iter()
     .collect();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn na_adjective_form(forms: &[GrammaticalForm]) -> Vec<(String, String)> {
+        let tag = String::from("adj-na");
+        generate_all_na_adjective_conjugations("静か", "しずか", vec![&tag])
+            .unwrap()
+            .into_iter()
+            .filter(|conjugation| conjugation.grammatical_forms == forms)
+            .map(|conjugation| (conjugation.kanji_form, conjugation.kana_form))
+            .collect()
+    }
+
+    #[test]
+    fn test_mistagged_adjective() {
+        let tag = String::from("adj-i");
+        assert!(generate_all_adjective_conjugations("x", "", vec![&tag]).is_none());
+        assert!(generate_all_adjective_conjugations("静か", "しずか", vec![&tag]).is_none());
+        assert!(generate_all_adjective_conjugations("高い", "たかい", vec![&tag]).is_some());
+    }
+
+    #[test]
+    fn test_na_adjective_plain_forms() {
+        use GrammaticalForm::*;
+        assert_eq!(
+            na_adjective_form(&[Predicative]),
+            vec![("静かだ".to_string(), "しずかだ".to_string())]
+        );
+        assert_eq!(
+            na_adjective_form(&[Negative]),
+            vec![
+                ("静かじゃない".to_string(), "しずかじゃない".to_string()),
+                ("静かではない".to_string(), "しずかではない".to_string())
+            ]
+        );
+        assert_eq!(
+            na_adjective_form(&[Past]),
+            vec![("静かだった".to_string(), "しずかだった".to_string())]
+        );
+        assert_eq!(
+            na_adjective_form(&[Negative, Past]),
+            vec![
+                (
+                    "静かじゃなかった".to_string(),
+                    "しずかじゃなかった".to_string()
+                ),
+                (
+                    "静かではなかった".to_string(),
+                    "しずかではなかった".to_string()
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn test_na_adjective_polite_forms() {
+        use GrammaticalForm::*;
+        assert_eq!(
+            na_adjective_form(&[Polite]),
+            vec![("静かです".to_string(), "しずかです".to_string())]
+        );
+        assert_eq!(
+            na_adjective_form(&[Polite, Past]),
+            vec![("静かでした".to_string(), "しずかでした".to_string())]
+        );
+        assert_eq!(
+            na_adjective_form(&[Polite, Negative]),
+            vec![
+                (
+                    "静かじゃありません".to_string(),
+                    "しずかじゃありません".to_string()
+                ),
+                (
+                    "静かではありません".to_string(),
+                    "しずかではありません".to_string()
+                )
+            ]
+        );
+        assert_eq!(
+            na_adjective_form(&[Polite, Negative, Past]),
+            vec![
+                (
+                    "静かじゃありませんでした".to_string(),
+                    "しずかじゃありませんでした".to_string()
+                ),
+                (
+                    "静かではありませんでした".to_string(),
+                    "しずかではありませんでした".to_string()
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn test_na_adjective_connective_forms() {
+        use GrammaticalForm::*;
+        assert_eq!(
+            na_adjective_form(&[TeForm]),
+            vec![("静かで".to_string(), "しずかで".to_string())]
+        );
+        assert_eq!(
+            na_adjective_form(&[Negative, TeForm]),
+            vec![
+                ("静かじゃなくて".to_string(), "しずかじゃなくて".to_string()),
+                ("静かではなくて".to_string(), "しずかではなくて".to_string())
+            ]
+        );
+        assert_eq!(
+            na_adjective_form(&[Adverbial]),
+            vec![("静かに".to_string(), "しずかに".to_string())]
+        );
+        assert_eq!(
+            na_adjective_form(&[Attributive]),
+            vec![("静かな".to_string(), "しずかな".to_string())]
+        );
+        assert_eq!(
+            na_adjective_form(&[ProvisionalConditional]),
+            vec![("静かなら".to_string(), "しずかなら".to_string())]
+        );
+        assert_eq!(
+            na_adjective_form(&[Conditional]),
+            vec![("静かだったら".to_string(), "しずかだったら".to_string())]
+        );
+    }
+
+    fn i_adjective_form(
+        adjective: &str,
+        reading: &str,
+        tag: &str,
+        forms: &[GrammaticalForm],
+    ) -> Vec<(String, String)> {
+        let tag = String::from(tag);
+        generate_all_adjective_conjugations(adjective, reading, vec![&tag])
+            .unwrap()
+            .into_iter()
+            .filter(|conjugation| conjugation.grammatical_forms == forms)
+            .map(|conjugation| (conjugation.kanji_form, conjugation.kana_form))
+            .collect()
+    }
+
+    #[test]
+    fn test_i_adjective_forms() {
+        use GrammaticalForm::*;
+        let takai = |forms| i_adjective_form("高い", "たかい", "adj-i", forms);
+        assert_eq!(
+            takai(&[Negative, TeForm]),
+            vec![("高くなくて".to_string(), "たかくなくて".to_string())]
+        );
+        assert_eq!(
+            takai(&[TeForm]),
+            vec![("高くて".to_string(), "たかくて".to_string())]
+        );
+        assert_eq!(
+            takai(&[ProvisionalConditional]),
+            vec![("高ければ".to_string(), "たかければ".to_string())]
+        );
+        assert_eq!(
+            takai(&[Conditional]),
+            vec![("高かったら".to_string(), "たかかったら".to_string())]
+        );
+        assert_eq!(
+            takai(&[Volitional]),
+            vec![("高かろう".to_string(), "たかかろう".to_string())]
+        );
+        assert_eq!(
+            takai(&[Polite]),
+            vec![("高いです".to_string(), "たかいです".to_string())]
+        );
+        assert_eq!(
+            takai(&[Polite, Negative]),
+            vec![
+                ("高くありません".to_string(), "たかくありません".to_string()),
+                ("高くないです".to_string(), "たかくないです".to_string())
+            ]
+        );
+        assert_eq!(
+            takai(&[Appearance]),
+            vec![("高そう".to_string(), "たかそう".to_string())]
+        );
+        assert_eq!(
+            takai(&[Nominal]),
+            vec![("高さ".to_string(), "たかさ".to_string())]
+        );
+        assert_eq!(
+            takai(&[Excessive]),
+            vec![("高すぎる".to_string(), "たかすぎる".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_ii_adjective_forms() {
+        use GrammaticalForm::*;
+        let ii = |forms| i_adjective_form("良い", "いい", "adj-ix", forms);
+        assert_eq!(
+            ii(&[Negative]),
+            vec![("良くない".to_string(), "よくない".to_string())]
+        );
+        assert_eq!(
+            ii(&[Past]),
+            vec![("良かった".to_string(), "よかった".to_string())]
+        );
+        assert_eq!(
+            ii(&[Polite]),
+            vec![("良いです".to_string(), "いいです".to_string())]
+        );
+        assert_eq!(
+            ii(&[Appearance]),
+            vec![("良さそう".to_string(), "よさそう".to_string())]
+        );
+        assert_eq!(
+            i_adjective_form("かっこいい", "", "adj-ix", &[ProvisionalConditional]),
+            vec![("かっこよければ".to_string(), "かっこよければ".to_string())]
+        );
+        assert_eq!(
+            i_adjective_form("良い", "よい", "adj-i", &[Nominal]),
+            vec![("良さ".to_string(), "よさ".to_string())]
+        );
+        assert_eq!(
+            i_adjective_form("良い", "よい", "adj-i", &[Appearance]),
+            vec![("良さそう".to_string(), "よさそう".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_non_na_adjective() {
+        let tag = String::from("adj-i");
+        assert!(generate_all_na_adjective_conjugations("高い", "たかい", vec![&tag]).is_none());
+    }
+}
@@ -142,6 +142,21 @@ impl Key<String> for Tag {
     }
 }
 
+impl Tag {
+    #[cfg(test)]
+    pub fn new(tag: &str, category: &str) -> Tag {
+        Tag {
+            tag: tag.to_string(),
+            category: category.to_string(),
+            description: String::new(),
+        }
+    }
+
+    pub fn is_part_of_speech(&self) -> bool {
+        self.category == "partOfSpeech"
+    }
+}
+
 impl FromParsed<ParseTag> for Tag {
     fn from_parsed(parsed: ParseTag, _tags: Option<&HashMap<String, Tag>>) -> Self {
         Tag {
@@ -217,9 +232,9 @@ pub struct Kanjium {
     pub pitch: Pitch,
 }
 
-impl Key<String> for Kanjium {
-    fn key(&self) -> String {
-        self.vocabulary.clone()
+impl Key<(String, String)> for Kanjium {
+    fn key(&self) -> (String, String) {
+        (self.vocabulary.clone(), self.pitch.reading.clone())
     }
 }
 
@@ -257,6 +272,16 @@ pub struct Pitches {
     tags: HashMap<String, Tag>,
 }
 
+impl Pitches {
+    pub fn position(&self) -> usize {
+        self.position as usize
+    }
+
+    pub fn tags(&self) -> impl Iterator<Item = &Tag> {
+        self.tags.values()
+    }
+}
+
 impl FromParsed<ParsePitches> for Pitches {
     fn from_parsed(parsed: ParsePitches, tags: Option<&HashMap<String, Tag>>) -> Self {
         Pitches {
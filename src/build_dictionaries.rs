@@ -1,7 +1,12 @@
 use crate::{
     basic_dictionaries::*,
-    composite_dictionaries::{DictionaryEntry, Kanji, Name, Radical, Word},
+    composite_dictionaries::{DictionaryEntry, ExampleWords, Kanji, Name, Radical, Word},
     dictionary_paths::*,
+    example_sentences::ExampleSentences,
+    furigana::{base_reading, normalize_kanji_reading, Furigana},
+    parse_example_sentences::parse_example_sentences_from_tsv,
+    similar_kanji::find_similar_kanji,
+    Dictionary,
 };
 use anyhow::Result;
 use core::fmt;
@@ -10,12 +15,15 @@ use itertools::Itertools;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::{
+    cmp::Reverse,
     collections::{HashMap, HashSet},
     error::Error,
     fs::File,
     path::Path,
 };
 
+const EXAMPLE_WORDS_PER_KANJI: usize = 10;
+
 pub trait FromParsed<P> {
     fn from_parsed(parsed: P, tags: Option<&HashMap<String, Tag>>) -> Self;
 }
@@ -111,19 +119,33 @@ pub fn build_composite_dicts() -> Result<()> {
         krad,
         radk,
     );
-    match export_vec_as_bin(&composite_dicts, DICTIONARY_ENTRIES) {
+    match export_as_bin(&composite_dicts, DICTIONARY_ENTRIES) {
         Ok(_) => println!("Succesfully exported dictionary"),
         Err(e) => panic!("Failed to export dictionary, reason: {}", e),
     }
+    let dict = Dictionary::create(&composite_dicts);
+    let sentences = match Path::new(TATOEBA_SENTENCES).exists() {
+        true => parse_example_sentences_from_tsv(
+            TATOEBA_SENTENCES,
+            Some(TATOEBA_LINKS).filter(|links| Path::new(links).exists()),
+            &dict,
+        )?,
+        false => parse_example_sentences_from_tsv(EXAMPLE_SENTENCES_TSV, None, &dict)?,
+    };
+    match export_as_bin(&ExampleSentences::create(&sentences), EXAMPLE_SENTENCES) {
+        Ok(_) => println!("Succesfully exported example sentences"),
+        Err(e) => panic!("Failed to export example sentences, reason: {}", e),
+    }
     Ok(())
 }
 
-pub fn export_vec_as_bin<D: Serialize>(dicts: &Vec<D>, export_path: &str) -> Result<()> {
-    let encoded: Vec<u8> = bincode::serialize(&dicts)?;
+pub fn export_as_bin<D: Serialize>(data: &D, export_path: &str) -> Result<()> {
+    let encoded: Vec<u8> = bincode::serialize(data)?;
     std::fs::write(export_path, encoded)?;
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn assemble_composite_dicts(
     jmdicts: Vec<Jmdict>,
     jmnedicts: Vec<Jmdict>,
@@ -137,9 +159,16 @@ fn assemble_composite_dicts(
     let innocent_vocab_map: HashMap<String, Innocent> = hashmap_from_dicts(innocent_vocab);
     let innocent_kanji_map: HashMap<String, Innocent> = hashmap_from_dicts(innocent_kanji);
     let krad_map: HashMap<String, Krad> = hashmap_from_dicts(krad);
-    let kanjium_map: HashMap<String, Kanjium> = hashmap_from_dicts(kanjium);
+    let kanjium_map: HashMap<(String, String), Kanjium> = hashmap_from_dicts(kanjium);
     let kanji_dicts: Vec<Kanji> = assemble_kanji_dicts(kanjidic, &innocent_kanji_map, &krad_map);
     let word_dicts: Vec<Word> = assemble_word_dicts(jmdicts, &innocent_vocab_map, &kanjium_map);
+    let kanji_dicts: Vec<Kanji> = assemble_kanji_example_words(kanji_dicts, &word_dicts);
+    let similar_kanji: Vec<Vec<String>> = find_similar_kanji(&kanji_dicts);
+    let kanji_dicts: Vec<Kanji> = kanji_dicts
+        .into_iter()
+        .zip(similar_kanji)
+        .map(|(kanji, similar)| kanji.with_similar(similar))
+        .collect();
     let name_dicts: Vec<Name> = assemble_name_dicts(jmnedicts);
     let radical_dicts: Vec<Radical> = assemble_radical_dicts(radk);
     kanji_dicts
@@ -154,7 +183,7 @@ fn assemble_composite_dicts(
 fn assemble_word_dicts(
     jmdicts: Vec<Jmdict>,
     innocent_map: &HashMap<String, Innocent>,
-    kanjium_map: &HashMap<String, Kanjium>,
+    kanjium_map: &HashMap<(String, String), Kanjium>,
 ) -> Vec<Word> {
     jmdicts
         .into_iter()
@@ -180,7 +209,7 @@ fn assemble_word_dicts(
             let innocent_value = innocent_map
                 .get(entry.vocabulary.as_str())
                 .or(innocent_map.get(entry.reading.as_str()));
-            let kanjium_value = kanjium_map.get(entry.vocabulary.as_str());
+            let kanjium_value = kanjium_map.get(&(entry.vocabulary.clone(), entry.reading.clone()));
             Word::from(entry, innocent_value, kanjium_value)
         })
         .collect()
@@ -207,6 +236,64 @@ fn assemble_kanji_dicts(
         .collect()
 }
 
+fn assemble_kanji_example_words(kanji_dicts: Vec<Kanji>, word_dicts: &[Word]) -> Vec<Kanji> {
+    let kanji_readings: HashMap<char, Vec<String>> = kanji_dicts
+        .iter()
+        .filter_map(|kanji| {
+            let readings = kanji.readings().map(normalize_kanji_reading).collect();
+            Some((kanji.kanji().chars().next()?, readings))
+        })
+        .collect();
+    let mut words_by_kanji: HashMap<char, Vec<&Word>> = HashMap::new();
+    for word in word_dicts.iter().filter(|word| word.frequency().is_some()) {
+        for kanji in word.vocabulary.chars().unique() {
+            if kanji_readings.contains_key(&kanji) {
+                words_by_kanji.entry(kanji).or_default().push(word);
+            }
+        }
+    }
+    kanji_dicts
+        .into_par_iter()
+        .map(|kanji| {
+            let Some(character) = kanji.kanji().chars().next() else {
+                return kanji;
+            };
+            let readings = &kanji_readings[&character];
+            let words = words_by_kanji
+                .get(&character)
+                .into_iter()
+                .flatten()
+                .sorted_by_key(|word| (Reverse(word.frequency()), word.key()))
+                .unique_by(|word| &word.vocabulary)
+                .take(EXAMPLE_WORDS_PER_KANJI);
+            let mut example_words: Vec<ExampleWords> = Vec::new();
+            for word in words {
+                let furigana = Furigana::align_with(&word.vocabulary, &word.reading, &|kanji| {
+                    kanji_readings.get(&kanji).cloned().unwrap_or_default()
+                });
+                let reading = furigana
+                    .segments
+                    .into_iter()
+                    .find(|segment| segment.text == kanji.kanji())
+                    .and_then(|segment| segment.reading)
+                    .map(|reading| base_reading(readings, &reading).cloned().unwrap_or(reading));
+                let example = (word.vocabulary.clone(), word.reading.clone());
+                match example_words
+                    .iter_mut()
+                    .find(|examples| examples.reading == reading)
+                {
+                    Some(examples) => examples.words.push(example),
+                    None => example_words.push(ExampleWords {
+                        reading,
+                        words: vec![example],
+                    }),
+                }
+            }
+            kanji.with_example_words(example_words)
+        })
+        .collect()
+}
+
 fn assemble_radical_dicts(radk: Vec<Radk>) -> Vec<Radical> {
     radk.into_iter().map(Radical::from).collect()
 }
@@ -239,3 +326,40 @@ impl fmt::Display for ParsingError {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{kanji, word};
+
+    #[test]
+    fn test_assemble_kanji_example_words() {
+        let kanji_dicts = vec![
+            kanji("生", "セイ ショウ", "い.きる う.まれる なま", &[]),
+            kanji("先", "セン", "さき", &[]),
+            kanji("活", "カツ", "い.きる", &[]),
+        ];
+        let word_dicts = vec![
+            word("生活", "せいかつ", &["n"], Some(1000)),
+            word("生まれる", "うまれる", &["v1"], Some(2000)),
+            word("先生", "せんせい", &["n"], Some(3000)),
+            word("生", "なま", &["n"], None),
+        ];
+        let kanji_dicts = assemble_kanji_example_words(kanji_dicts, &word_dicts);
+        let examples: Vec<(Option<&str>, Vec<&str>)> = kanji_dicts[0]
+            .example_words()
+            .iter()
+            .map(|examples| {
+                let words = examples.words.iter().map(|(word, _)| word.as_str());
+                (examples.reading.as_deref(), words.collect())
+            })
+            .collect();
+        assert_eq!(
+            examples,
+            [
+                (Some("せい"), vec!["先生", "生活"]),
+                (Some("う"), vec!["生まれる"]),
+            ]
+        );
+    }
+}
@@ -1,28 +1,33 @@
 use crate::{
-    adjective_conjugation_utils::generate_all_adjective_conjugations,
+    adjective_conjugation_utils::{
+        generate_all_adjective_conjugations, generate_all_na_adjective_conjugations,
+    },
     basic_dictionaries::*,
     build_dictionaries::Key,
-    query::Query,
+    pitch_accent::{adjective_accent, verb_accent, PitchAccent},
+    query::{MatchReason, Query},
     verb_conjugation_utils::{generate_all_verb_conjugations, ConjugatedWord},
 };
+use anyhow::Result;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
     collections::HashMap,
     fmt::{self, Display},
+    ops::RangeInclusive,
 };
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Word {
     pub vocabulary: String,
     pub reading: String,
+    romaji: String,
     meanings: Vec<String>,
     tags: HashMap<String, Tag>,
     id: u32,
     frequency: Option<u32>,
     pitches: Option<Vec<Pitches>>,
-    conjugations: Vec<ConjugatedWord>,
 }
 
 impl Key<u32> for Word {
@@ -39,15 +44,14 @@ impl fmt::Display for Word {
         for (index, meaning) in self.meanings.iter().enumerate() {
             writeln!(f, "{}. {}", index + 1, meaning)?;
         }
-        writeln!(
-            f,
-            "{} {}",
-            bold("Conjugations:"),
-            self.conjugations
-                .iter()
-                .map(|conj| &conj.kanji_form)
-                .join("、")
-        )?;
+        if let Ok(conjugations) = self.conjugations() {
+            writeln!(
+                f,
+                "{} {}",
+                bold("Conjugations:"),
+                conjugations.iter().map(|conj| &conj.kanji_form).join("、")
+            )?;
+        }
         let tags = self
             .tags
             .values()
@@ -56,9 +60,20 @@ impl fmt::Display for Word {
         if let Some(freq) = self.frequency {
             writeln!(f, "{} {}", bold("Frequency:"), freq)?;
         }
-        /* if let Some(pitches) = self.pitches.clone() {
-            writeln!(f, "{:#?}", pitches)?;
-        } */
+        for (accent, pitch) in self.pitch_accents() {
+            let tags = pitch.tags().map(|tag| tag.description.clone()).join(", ");
+            writeln!(
+                f,
+                "{} {} [{}]",
+                bold("Pitch:"),
+                accent.pattern(),
+                pitch.position()
+            )?;
+            writeln!(f, "{}", accent)?;
+            if !tags.is_empty() {
+                writeln!(f, "({})", tags)?;
+            }
+        }
         writeln!(f, "{} {}", bold("ID:"), self.id)?;
         if !tags.is_empty() {
             writeln!(f, "{}", tags)?;
@@ -75,8 +90,6 @@ impl Query for Word {
             .chain(std::iter::once(self.vocabulary.as_str()))
             .chain(std::iter::once(self.reading.as_str()))
             .chain(self.tags.keys().map(AsRef::as_ref))
-            .chain(self.conjugations.iter().map(|con| &*con.kana_form))
-            .chain(self.conjugations.iter().map(|con| &*con.kanji_form))
             .collect()
     }
 }
@@ -86,26 +99,80 @@ impl Word {
         Word {
             vocabulary: jmdict.vocabulary.clone(),
             reading: jmdict.reading.clone(),
+            romaji: jmdict.romaji.clone(),
             tags: jmdict.tags.clone(),
             meanings: jmdict.meanings.clone(),
             id: jmdict.id,
             frequency: innocent.map(|i| i.frequency),
             pitches: kanjium.map(|k| k.pitch.pitches.clone()),
-            conjugations: {
-                generate_all_verb_conjugations(
-                    &jmdict.vocabulary,
-                    &jmdict.reading,
-                    jmdict.tags.keys().collect(),
-                )
-                .or(generate_all_adjective_conjugations(
-                    &jmdict.vocabulary,
-                    &jmdict.reading,
-                    jmdict.tags.keys().collect(),
-                ))
-                .unwrap_or_default()
-            },
         }
     }
+
+    pub fn tags(&self) -> impl Iterator<Item = &str> {
+        self.tags.keys().map(AsRef::as_ref)
+    }
+
+    pub fn frequency(&self) -> Option<u32> {
+        self.frequency
+    }
+
+    pub fn romaji(&self) -> &str {
+        &self.romaji
+    }
+
+    pub fn pitch_accents(&self) -> impl Iterator<Item = (PitchAccent, &Pitches)> {
+        self.pitches
+            .iter()
+            .flatten()
+            .map(|pitch| (PitchAccent::new(&self.reading, pitch.position()), pitch))
+    }
+
+    pub fn conjugations(&self) -> Result<Vec<ConjugatedWord>> {
+        let tags: Vec<&String> = self.tags.keys().collect();
+        let accent = self.pitches.iter().flatten().next().map(Pitches::position);
+        let reading = match self.reading.as_str() {
+            "" => &self.vocabulary,
+            reading => reading,
+        };
+        if let Some(mut conjugations) =
+            generate_all_verb_conjugations(&self.vocabulary, &self.reading, tags.clone())?
+        {
+            // suru verbs keep the accent of their noun unless it is flat
+            let suru_noun = self.tags.contains_key("vs");
+            let reading = match suru_noun {
+                true => [reading, "する"].concat(),
+                false => reading.to_string(),
+            };
+            for conjugation in conjugations.iter_mut() {
+                let forms = &conjugation.grammatical_forms;
+                conjugation.pitch_accent =
+                    accent.and_then(|accent| match suru_noun && accent > 0 {
+                        true => Some(accent),
+                        false => verb_accent(&reading, accent, &conjugation.kana_form, forms),
+                    });
+            }
+            return Ok(conjugations);
+        }
+        if let Some(mut conjugations) =
+            generate_all_adjective_conjugations(&self.vocabulary, &self.reading, tags.clone())
+        {
+            for conjugation in conjugations.iter_mut() {
+                conjugation.pitch_accent = accent.and_then(|accent| {
+                    adjective_accent(
+                        reading,
+                        accent,
+                        &conjugation.kana_form,
+                        &conjugation.grammatical_forms,
+                    )
+                });
+            }
+            return Ok(conjugations);
+        }
+        Ok(
+            generate_all_na_adjective_conjugations(&self.vocabulary, &self.reading, tags)
+                .unwrap_or_default(),
+        )
+    }
 }
 
 impl PartialEq for Word {
@@ -224,6 +291,16 @@ pub struct Kanji {
     radicals: Option<Vec<String>>,
     tags: HashMap<String, Tag>,
     attributes: HashMap<String, String>,
+    example_words: Vec<ExampleWords>,
+    similar: Vec<String>,
+}
+
+/// Frequent words in which a kanji is read as `reading`, `None` for readings
+/// that only apply to the whole word.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExampleWords {
+    pub reading: Option<String>,
+    pub words: Vec<(String, String)>,
 }
 
 impl fmt::Display for Kanji {
@@ -241,8 +318,18 @@ impl fmt::Display for Kanji {
         if let Some(frequency) = self.frequency {
             writeln!(f, "{}, {}", bold("Frequency:"), frequency)?;
         }
-        if let Some(jlpt) = self.jlpt {
-            writeln!(f, "{} level N{}", bold("JLPT"), jlpt)?;
+        match self.jlpt() {
+            Some(levels) if levels.start() == levels.end() => {
+                writeln!(f, "{} level N{}", bold("JLPT"), levels.start())?
+            }
+            Some(levels) => writeln!(
+                f,
+                "{} level N{}/N{}",
+                bold("JLPT"),
+                levels.end(),
+                levels.start()
+            )?,
+            None => {}
         }
         if let Some(grade) = self.grade {
             writeln!(f, "Taught in {} {}", bold("grade"), grade)?;
@@ -250,6 +337,24 @@ impl fmt::Display for Kanji {
         if let Some(radicals) = self.radicals.clone() {
             writeln!(f, "{} {}", bold("Radicals:"), radicals.iter().join("、"))?;
         }
+        if !self.similar.is_empty() {
+            writeln!(f, "{} {}", bold("Similar:"), self.similar.iter().join("、"))?;
+        }
+        if !self.example_words.is_empty() {
+            writeln!(f, "{}", bold("Words:"))?;
+        }
+        for examples in self.example_words.iter() {
+            writeln!(
+                f,
+                "{}: {}",
+                examples.reading.as_deref().unwrap_or("other"),
+                examples
+                    .words
+                    .iter()
+                    .map(|(vocabulary, reading)| format!("{} ({})", vocabulary, reading))
+                    .join("、")
+            )?;
+        }
         let tags = self
             .tags
             .values()
@@ -325,8 +430,79 @@ impl Kanji {
                 .clone()
                 .extract_if(|key, _| !["strokes", "ucs", "jlpt", "grade"].contains(&key.as_str()))
                 .collect(),
+            example_words: Vec::new(),
+            similar: Vec::new(),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn example_words(&self) -> &[ExampleWords] {
+        &self.example_words
+    }
+
+    pub fn with_example_words(self, example_words: Vec<ExampleWords>) -> Kanji {
+        Kanji {
+            example_words,
+            ..self
+        }
+    }
+
+    pub fn with_similar(self, similar: Vec<String>) -> Kanji {
+        Kanji { similar, ..self }
+    }
+
+    pub fn similar(&self) -> impl Iterator<Item = &str> {
+        self.similar.iter().map(AsRef::as_ref)
+    }
+
+    pub fn meanings(&self) -> impl Iterator<Item = &str> {
+        self.meanings.iter().map(AsRef::as_ref)
+    }
+
+    pub fn kanji(&self) -> &str {
+        &self.kanji
+    }
+
+    pub fn strokes(&self) -> u8 {
+        self.strokes
+    }
+
+    pub fn frequency(&self) -> Option<u32> {
+        self.frequency
+    }
+
+    /// N-levels of the kanji, kanjidic still has the four levels used before
+    /// 2010 and their level 2 was split into N3 and N2.
+    pub fn jlpt(&self) -> Option<RangeInclusive<u8>> {
+        match self.jlpt? {
+            4 => Some(5..=5),
+            3 => Some(4..=4),
+            2 => Some(2..=3),
+            1 => Some(1..=1),
+            _ => None,
         }
     }
+
+    pub fn grade(&self) -> Option<u8> {
+        self.grade
+    }
+
+    pub fn radicals(&self) -> impl Iterator<Item = &str> {
+        self.radicals.iter().flatten().map(AsRef::as_ref)
+    }
+
+    pub fn attributes(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.attributes
+            .iter()
+            .map(|(attribute, value)| (attribute.as_str(), value.as_str()))
+    }
+
+    pub fn readings(&self) -> impl Iterator<Item = &str> {
+        self.kun_yomi
+            .iter()
+            .chain(self.on_yomi.iter())
+            .map(AsRef::as_ref)
+    }
 }
 
 impl PartialEq for Kanji {
@@ -386,6 +562,22 @@ impl Radical {
             kanji: radk.kanji.chars().collect(),
         }
     }
+
+    pub fn radical(&self) -> &str {
+        &self.radical
+    }
+
+    pub fn strokes(&self) -> u8 {
+        self.strokes
+    }
+
+    pub fn is_part_of(&self, kanji: &str) -> bool {
+        self.kanji.contains(kanji)
+    }
+
+    pub fn kanji(&self) -> impl Iterator<Item = char> + '_ {
+        self.kanji.chars()
+    }
 }
 
 impl PartialEq for Radical {
@@ -457,6 +649,38 @@ impl DictionaryEntry {
         }
     }
 
+    /// The terms an entry is written or read as, without its meanings.
+    pub fn headwords(&self) -> Vec<&str> {
+        match self {
+            DictionaryEntry::Kanji(entry) => std::iter::once(entry.kanji.as_str())
+                .chain(entry.kun_yomi.iter().map(AsRef::as_ref))
+                .chain(entry.on_yomi.iter().map(AsRef::as_ref))
+                .collect(),
+            DictionaryEntry::Word(entry) => vec![&entry.vocabulary, &entry.reading],
+            DictionaryEntry::Name(entry) => vec![&entry.name, &entry.reading],
+            DictionaryEntry::Radical(entry) => vec![&entry.radical],
+        }
+    }
+
+    pub fn meanings(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        match self {
+            DictionaryEntry::Kanji(entry) => Box::new(entry.meanings()),
+            DictionaryEntry::Word(entry) => Box::new(entry.meanings.iter().map(AsRef::as_ref)),
+            DictionaryEntry::Name(entry) => Box::new(entry.translations.iter().map(AsRef::as_ref)),
+            DictionaryEntry::Radical(_) => Box::new(std::iter::empty()),
+        }
+    }
+
+    pub fn tags(&self) -> Box<dyn Iterator<Item = (&str, &Tag)> + '_> {
+        let tags = match self {
+            DictionaryEntry::Kanji(entry) => &entry.tags,
+            DictionaryEntry::Word(entry) => &entry.tags,
+            DictionaryEntry::Name(entry) => &entry.tags,
+            DictionaryEntry::Radical(_) => return Box::new(std::iter::empty()),
+        };
+        Box::new(tags.iter().map(|(name, tag)| (name.as_str(), tag)))
+    }
+
     pub fn frequency(&self) -> Option<u32> {
         match self {
             DictionaryEntry::Kanji(entry) => entry.frequency,
@@ -466,16 +690,26 @@ impl DictionaryEntry {
         }
     }
 
-    pub fn primary_word(&self) -> &str {
+    pub fn match_reason(&self, key: &str) -> MatchReason {
+        let key = key.to_string();
         match self {
-            DictionaryEntry::Kanji(entry) => &entry.kanji,
-            DictionaryEntry::Word(entry) => &entry.vocabulary,
-            DictionaryEntry::Name(entry) => &entry.name,
-            DictionaryEntry::Radical(entry) => &entry.radical,
+            DictionaryEntry::Kanji(entry) if entry.kanji == key => MatchReason::Vocabulary(key),
+            DictionaryEntry::Kanji(entry)
+                if entry.kun_yomi.contains(&key) || entry.on_yomi.contains(&key) =>
+            {
+                MatchReason::Reading(key)
+            }
+            DictionaryEntry::Word(entry) if entry.vocabulary == key => MatchReason::Vocabulary(key),
+            DictionaryEntry::Word(entry) if entry.reading == key => MatchReason::Reading(key),
+            DictionaryEntry::Word(entry) if entry.tags.contains_key(&key) => MatchReason::Tag(key),
+            DictionaryEntry::Name(entry) if entry.name == key => MatchReason::Vocabulary(key),
+            DictionaryEntry::Name(entry) if entry.reading == key => MatchReason::Reading(key),
+            DictionaryEntry::Radical(_) => MatchReason::Vocabulary(key),
+            _ => MatchReason::Meaning(key),
         }
     }
 }
 
-fn bold(str: &str) -> String {
+pub fn bold(str: &str) -> String {
     format!("\x1b[1m{}\x1b[0m", str)
 }
@@ -0,0 +1,200 @@
+use crate::{
+    composite_dictionaries::{bold, Word},
+    display::pad,
+    pitch_accent::PitchAccent,
+    verb_conjugation_utils::{ConjugatedWord, GrammaticalForm},
+};
+use anyhow::Result;
+use itertools::Itertools;
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Politeness {
+    Plain,
+    Polite,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Polarity {
+    Affirmative,
+    Negative,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Tense {
+    NonPast,
+    Past,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConjugationCell {
+    pub politeness: Politeness,
+    pub polarity: Polarity,
+    pub tense: Tense,
+    pub kanji_form: String,
+    pub kana_form: String,
+    pub pitch_accent: Option<usize>,
+    pub grammatical_forms: Vec<GrammaticalForm>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConjugationRow {
+    pub label: String,
+    pub cells: Vec<ConjugationCell>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConjugationTable {
+    pub vocabulary: String,
+    pub reading: String,
+    pub rows: Vec<ConjugationRow>,
+}
+
+impl ConjugationTable {
+    pub fn from_word(word: &Word) -> Result<Option<ConjugationTable>> {
+        let conjugations = word.conjugations()?;
+        if conjugations.is_empty() {
+            return Ok(None);
+        }
+        let dictionary_form = ConjugatedWord {
+            kanji_form: word.vocabulary.clone(),
+            kana_form: word.reading.clone(),
+            pitch_accent: word
+                .pitch_accents()
+                .next()
+                .map(|(_, pitches)| pitches.position()),
+            grammatical_forms: Vec::new(),
+        };
+        // na-adjectives and suru-nouns carry their own form for the dictionary form cell
+        let has_predicative = conjugations.iter().any(|conjugation| {
+            conjugation.grammatical_forms.is_empty()
+                || conjugation.grammatical_forms == [GrammaticalForm::Predicative]
+        });
+        let mut rows: Vec<ConjugationRow> = Vec::new();
+        for conjugation in std::iter::once(dictionary_form)
+            .filter(|_| !has_predicative)
+            .chain(conjugations)
+        {
+            let label = row_label(&conjugation.grammatical_forms);
+            let cell = ConjugationCell::from(conjugation);
+            match rows.iter_mut().find(|row| row.label == label) {
+                Some(row) => row.cells.push(cell),
+                None => rows.push(ConjugationRow {
+                    label,
+                    cells: vec![cell],
+                }),
+            }
+        }
+        Ok(Some(ConjugationTable {
+            vocabulary: word.vocabulary.clone(),
+            reading: word.reading.clone(),
+            rows,
+        }))
+    }
+}
+
+impl From<ConjugatedWord> for ConjugationCell {
+    fn from(conjugation: ConjugatedWord) -> Self {
+        let has = |form| conjugation.grammatical_forms.contains(&form);
+        ConjugationCell {
+            politeness: match has(GrammaticalForm::Polite) {
+                true => Politeness::Polite,
+                false => Politeness::Plain,
+            },
+            polarity: match has(GrammaticalForm::Negative) {
+                true => Polarity::Negative,
+                false => Polarity::Affirmative,
+            },
+            tense: match has(GrammaticalForm::Past) {
+                true => Tense::Past,
+                false => Tense::NonPast,
+            },
+            kanji_form: conjugation.kanji_form,
+            kana_form: conjugation.kana_form,
+            pitch_accent: conjugation.pitch_accent,
+            grammatical_forms: conjugation.grammatical_forms,
+        }
+    }
+}
+
+fn row_label(grammatical_forms: &[GrammaticalForm]) -> String {
+    let label = grammatical_forms
+        .iter()
+        .filter(|form| {
+            ![
+                GrammaticalForm::Polite,
+                GrammaticalForm::Negative,
+                GrammaticalForm::Past,
+                GrammaticalForm::Predicative,
+            ]
+            .contains(form)
+        })
+        .join(" ");
+    let mut chars = label.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::from("Indicative"),
+    }
+}
+
+impl fmt::Display for ConjugationTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} {} ({})",
+            bold("Conjugations of"),
+            self.vocabulary,
+            self.reading
+        )?;
+        for row in self.rows.iter() {
+            writeln!(f)?;
+            writeln!(f, "{}", bold(&row.label))?;
+            for politeness in [Politeness::Plain, Politeness::Polite] {
+                for tense in [Tense::NonPast, Tense::Past] {
+                    let cell_for = |polarity| {
+                        row.cells
+                            .iter()
+                            .find(|cell| {
+                                cell.politeness == politeness
+                                    && cell.tense == tense
+                                    && cell.polarity == polarity
+                            })
+                            .map(|cell| {
+                                let reading = match cell.pitch_accent {
+                                    Some(position) => {
+                                        PitchAccent::new(&cell.kana_form, position).marked()
+                                    }
+                                    None => cell.kana_form.clone(),
+                                };
+                                format!("{} ({})", cell.kanji_form, reading)
+                            })
+                    };
+                    let affirmative = cell_for(Polarity::Affirmative);
+                    let negative = cell_for(Polarity::Negative);
+                    if affirmative.is_none() && negative.is_none() {
+                        continue;
+                    }
+                    let heading = format!("{:?} {}", politeness, tense).to_lowercase();
+                    writeln!(
+                        f,
+                        "  {}{}{}",
+                        pad(&heading, 16),
+                        pad(&affirmative.unwrap_or_else(|| String::from("-")), 40),
+                        negative.unwrap_or_else(|| String::from("-"))
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Tense {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tense::NonPast => write!(f, "non-past"),
+            Tense::Past => write!(f, "past"),
+        }
+    }
+}
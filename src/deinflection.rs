@@ -0,0 +1,817 @@
+use crate::verb_conjugation_utils::{GrammaticalForm, SUPPLETIVE_VERBS};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+// the masu stem of ichidan verbs ends in a kana of the i or e row
+const ICHIDAN_STEM_ENDINGS: &str = "いきぎしじちぢにひびぴみりえけげせぜてでねへべぺめれ";
+// stands in for the last character of rules without an ending
+const EMPTY_ENDING: char = '\0';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordClass {
+    Ichidan,
+    Godan,
+    GodanAru,
+    GodanIku,
+    Kuru,
+    Suru,
+    SuruNoun,
+    Zuru,
+    IAdjective,
+    NaAdjective,
+    Masu,
+    Te,
+}
+
+impl WordClass {
+    const ALL: [WordClass; 12] = [
+        WordClass::Ichidan,
+        WordClass::Godan,
+        WordClass::GodanAru,
+        WordClass::GodanIku,
+        WordClass::Kuru,
+        WordClass::Suru,
+        WordClass::SuruNoun,
+        WordClass::Zuru,
+        WordClass::IAdjective,
+        WordClass::NaAdjective,
+        WordClass::Masu,
+        WordClass::Te,
+    ];
+
+    /// The most specific class for a JMdict part of speech tag.
+    pub fn from_tag(tag: &str) -> Option<WordClass> {
+        WordClass::ALL
+            .into_iter()
+            .rev()
+            .find(|class| class.matches_tag(tag))
+    }
+
+    pub fn matches_tag(&self, tag: &str) -> bool {
+        match self {
+            WordClass::Ichidan => matches!(tag, "v1" | "v1-s"),
+            WordClass::Godan => tag.starts_with("v5") || matches!(tag, "vn" | "vs-c"),
+            WordClass::GodanAru => tag == "v5aru",
+            WordClass::GodanIku => tag == "v5k-s",
+            WordClass::Kuru => tag == "vk",
+            WordClass::Suru => matches!(tag, "vs-i" | "vs-s"),
+            WordClass::SuruNoun => tag == "vs",
+            WordClass::Zuru => tag == "vz",
+            WordClass::IAdjective => matches!(tag, "adj-i" | "adj-ix"),
+            WordClass::NaAdjective => tag == "adj-na",
+            WordClass::Masu | WordClass::Te => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Deinflection {
+    pub term: String,
+    pub class: Option<WordClass>,
+    pub forms: Vec<GrammaticalForm>,
+}
+
+#[derive(Clone)]
+struct DeinflectionRule {
+    prefix: String,
+    inflected: String,
+    base: String,
+    classes_in: Vec<WordClass>,
+    class_out: WordClass,
+    forms: Vec<GrammaticalForm>,
+    // dictionary forms the rule must not lead to
+    exceptions: Vec<String>,
+}
+
+impl DeinflectionRule {
+    fn new(
+        inflected: &str,
+        base: &str,
+        classes_in: &[WordClass],
+        class_out: WordClass,
+        forms: &[GrammaticalForm],
+    ) -> Self {
+        DeinflectionRule {
+            prefix: String::new(),
+            inflected: inflected.to_string(),
+            base: base.to_string(),
+            classes_in: classes_in.to_vec(),
+            class_out,
+            forms: forms.to_vec(),
+            exceptions: Vec::new(),
+        }
+    }
+
+    fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    fn except(mut self, terms: &[&str]) -> Self {
+        self.exceptions = terms.iter().map(|term| term.to_string()).collect();
+        self
+    }
+
+    fn applies_to(&self, deinflection: &Deinflection) -> bool {
+        deinflection.term.len() >= self.prefix.len() + self.inflected.len()
+            && deinflection.term.starts_with(&self.prefix)
+            && deinflection.term.ends_with(&self.inflected)
+            && match deinflection.class {
+                None => true,
+                Some(class) => self.classes_in.contains(&class),
+            }
+    }
+}
+
+/// Walks the suffix rules back from `term` and returns every candidate
+/// dictionary form together with the grammatical forms that were stripped,
+/// ordered from the dictionary form outwards. The first element is always
+/// the unmodified term itself.
+///
+/// Most rules shorten the term. The others swap a keigo verb for its plain
+/// verb, turn ない back into ある or restore the te-form of an auxiliary, and
+/// none of their results can be taken apart by the same rule again, so the
+/// search terminates once no further rule matches.
+pub fn deinflect(term: &str) -> Vec<Deinflection> {
+    let mut results = vec![Deinflection {
+        term: term.to_string(),
+        class: None,
+        forms: Vec::new(),
+    }];
+    let mut index = 0;
+    while index < results.len() {
+        let current = results[index].clone();
+        for rule in rules_for(&current.term) {
+            if !rule.applies_to(&current) {
+                continue;
+            }
+            let stem = &current.term[rule.prefix.len()..current.term.len() - rule.inflected.len()];
+            let term = [stem, &rule.base].concat();
+            if term.is_empty() || rule.exceptions.contains(&term) {
+                continue;
+            }
+            results.push(Deinflection {
+                term,
+                class: Some(rule.class_out),
+                forms: rule
+                    .forms
+                    .iter()
+                    .chain(current.forms.iter())
+                    .copied()
+                    .collect(),
+            });
+        }
+        index += 1;
+    }
+    results
+}
+
+/// Whether some rule could take `term` apart, to skip `deinflect` for the
+/// many terms no rule applies to.
+pub fn may_be_inflected(term: &str) -> bool {
+    let Some(last) = term.chars().last() else {
+        return false;
+    };
+    let is_hiragana = matches!(last, 'ぁ'..='ゖ');
+    // a term ending in a kanji may still be an ichidan masu stem like 見
+    !is_hiragana
+        || ICHIDAN_STEM_ENDINGS.contains(last)
+        || rules_for(term).any(|rule| !rule.inflected.is_empty() && term.ends_with(&rule.inflected))
+}
+
+/// Rules whose ending is the last character of `term` or empty, in the order
+/// they were declared in.
+fn rules_for(term: &str) -> impl Iterator<Item = &'static DeinflectionRule> {
+    let by_last = term
+        .chars()
+        .last()
+        .and_then(|last| RULES_BY_LAST_CHAR.get(&last))
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let empty = RULES_BY_LAST_CHAR
+        .get(&EMPTY_ENDING)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    itertools::merge(by_last, empty).map(|&index| &DEINFLECTION_RULES[index])
+}
+
+fn godan_rules() -> Vec<DeinflectionRule> {
+    use GrammaticalForm::*;
+    use WordClass::*;
+    let rows = [
+        ("う", "わ", "い", "え", "お", "って", "った"),
+        ("く", "か", "き", "け", "こ", "いて", "いた"),
+        ("ぐ", "が", "ぎ", "げ", "ご", "いで", "いだ"),
+        ("す", "さ", "し", "せ", "そ", "して", "した"),
+        ("つ", "た", "ち", "て", "と", "って", "った"),
+        ("ぬ", "な", "に", "ね", "の", "んで", "んだ"),
+        ("ぶ", "ば", "び", "べ", "ぼ", "んで", "んだ"),
+        ("む", "ま", "み", "め", "も", "んで", "んだ"),
+        ("る", "ら", "り", "れ", "ろ", "って", "った"),
+    ];
+    rows.into_iter()
+        .flat_map(|(u, a, i, e, o, te, ta)| {
+            [
+                (format!("{a}ない"), vec![IAdjective], vec![Negative]),
+                (format!("{a}せる"), vec![Ichidan], vec![Causative]),
+                (format!("{a}す"), vec![Godan], vec![Causative, Short]),
+                (format!("{a}れる"), vec![Ichidan], vec![Passive]),
+                (i.to_string(), vec![], vec![MasuStem]),
+                (format!("{i}ます"), vec![Masu], vec![Polite]),
+                (format!("{i}たい"), vec![IAdjective], vec![Desire]),
+                (format!("{e}る"), vec![Ichidan], vec![Potential]),
+                (e.to_string(), vec![], vec![Imperative]),
+                (format!("{e}ば"), vec![], vec![ProvisionalConditional]),
+                (format!("{o}う"), vec![], vec![Volitional]),
+                (te.to_string(), vec![], vec![TeForm]),
+                (ta.to_string(), vec![], vec![Past]),
+                (format!("{ta}ら"), vec![], vec![Conditional]),
+            ]
+            .into_iter()
+            .map(move |(inflected, classes_in, forms)| {
+                DeinflectionRule::new(&inflected, u, &classes_in, Godan, &forms)
+            })
+        })
+        .chain([
+            // 行く and its compounds (v5k-s)
+            DeinflectionRule::new("って", "く", &[], GodanIku, &[TeForm]),
+            DeinflectionRule::new("った", "く", &[], GodanIku, &[Past]),
+            DeinflectionRule::new("ったら", "く", &[], GodanIku, &[Conditional]),
+            // 問う, 請う (v5u-s)
+            DeinflectionRule::new("うて", "う", &[], Godan, &[TeForm]),
+            DeinflectionRule::new("うた", "う", &[], Godan, &[Past]),
+            DeinflectionRule::new("うたら", "う", &[], Godan, &[Conditional]),
+            // ある (v5r-i)
+            DeinflectionRule::new("ない", "ある", &[IAdjective], Godan, &[Negative]),
+        ])
+        .collect()
+}
+
+fn ichidan_rules() -> Vec<DeinflectionRule> {
+    use GrammaticalForm::*;
+    use WordClass::*;
+    [
+        ("ない", vec![IAdjective], vec![Negative]),
+        ("させる", vec![Ichidan], vec![Causative]),
+        ("さす", vec![Godan], vec![Causative, Short]),
+        ("られる", vec![Ichidan], vec![Passive]),
+        ("られる", vec![Ichidan], vec![Potential]),
+        ("れる", vec![Ichidan], vec![Potential]),
+        ("", vec![], vec![MasuStem]),
+        ("ます", vec![Masu], vec![Polite]),
+        ("たい", vec![IAdjective], vec![Desire]),
+        ("ろ", vec![], vec![Imperative]),
+        ("れば", vec![], vec![ProvisionalConditional]),
+        ("よう", vec![], vec![Volitional]),
+        ("て", vec![], vec![TeForm]),
+        ("た", vec![], vec![Past]),
+        ("たら", vec![], vec![Conditional]),
+    ]
+    .into_iter()
+    .map(|(inflected, classes_in, forms)| {
+        DeinflectionRule::new(inflected, "る", &classes_in, Ichidan, &forms)
+    })
+    .collect()
+}
+
+fn kuru_rules() -> Vec<DeinflectionRule> {
+    use GrammaticalForm::*;
+    use WordClass::*;
+    // (dictionary form, negative stem, masu stem, provisional stem)
+    let stems = [("来る", "来", "来", "来"), ("くる", "こ", "き", "く")];
+    stems
+        .into_iter()
+        .flat_map(|(base, ko, ki, ku)| {
+            [
+                (format!("{ko}ない"), vec![IAdjective], vec![Negative]),
+                (format!("{ko}させる"), vec![Ichidan], vec![Causative]),
+                (format!("{ko}られる"), vec![Ichidan], vec![Passive]),
+                (format!("{ko}られる"), vec![Ichidan], vec![Potential]),
+                (format!("{ko}れる"), vec![Ichidan], vec![Potential]),
+                (ki.to_string(), vec![], vec![MasuStem]),
+                (format!("{ki}ます"), vec![Masu], vec![Polite]),
+                (format!("{ki}たい"), vec![IAdjective], vec![Desire]),
+                (format!("{ko}い"), vec![], vec![Imperative]),
+                (format!("{ku}れば"), vec![], vec![ProvisionalConditional]),
+                (format!("{ko}よう"), vec![], vec![Volitional]),
+                (format!("{ki}て"), vec![], vec![TeForm]),
+                (format!("{ki}た"), vec![], vec![Past]),
+                (format!("{ki}たら"), vec![], vec![Conditional]),
+            ]
+            .into_iter()
+            .map(move |(inflected, classes_in, forms)| {
+                DeinflectionRule::new(&inflected, base, &classes_in, Kuru, &forms)
+            })
+        })
+        .collect()
+}
+
+fn suru_rules() -> Vec<DeinflectionRule> {
+    use GrammaticalForm::*;
+    use WordClass::*;
+    [
+        ("しない", "する", vec![IAdjective], vec![Negative], Suru),
+        ("させる", "する", vec![Ichidan], vec![Causative], Suru),
+        ("される", "する", vec![Ichidan], vec![Passive], Suru),
+        ("できる", "する", vec![Ichidan], vec![Potential], Suru),
+        ("し", "する", vec![], vec![MasuStem], Suru),
+        ("します", "する", vec![Masu], vec![Polite], Suru),
+        ("したい", "する", vec![IAdjective], vec![Desire], Suru),
+        ("しろ", "する", vec![], vec![Imperative], Suru),
+        ("せよ", "する", vec![], vec![Imperative], Suru),
+        ("すれば", "する", vec![], vec![ProvisionalConditional], Suru),
+        ("しよう", "する", vec![], vec![Volitional], Suru),
+        ("して", "する", vec![], vec![TeForm], Suru),
+        ("した", "する", vec![], vec![Past], Suru),
+        ("したら", "する", vec![], vec![Conditional], Suru),
+        ("じない", "ずる", vec![IAdjective], vec![Negative], Zuru),
+        ("じさせる", "ずる", vec![Ichidan], vec![Causative], Zuru),
+        ("じられる", "ずる", vec![Ichidan], vec![Passive], Zuru),
+        ("じ", "ずる", vec![], vec![MasuStem], Zuru),
+        ("じます", "ずる", vec![Masu], vec![Polite], Zuru),
+        ("じたい", "ずる", vec![IAdjective], vec![Desire], Zuru),
+        ("じろ", "ずる", vec![], vec![Imperative], Zuru),
+        ("ずれば", "ずる", vec![], vec![ProvisionalConditional], Zuru),
+        ("じよう", "ずる", vec![], vec![Volitional], Zuru),
+        ("じて", "ずる", vec![], vec![TeForm], Zuru),
+        ("じた", "ずる", vec![], vec![Past], Zuru),
+        ("じたら", "ずる", vec![], vec![Conditional], Zuru),
+        ("する", "", vec![Suru], vec![], SuruNoun),
+    ]
+    .into_iter()
+    .map(|(inflected, base, classes_in, forms, class_out)| {
+        DeinflectionRule::new(inflected, base, &classes_in, class_out, &forms)
+    })
+    .collect()
+}
+
+fn adjective_rules() -> Vec<DeinflectionRule> {
+    use GrammaticalForm::*;
+    use WordClass::*;
+    [
+        ("くない", vec![IAdjective], vec![Negative]),
+        ("かった", vec![], vec![Past]),
+        ("くて", vec![], vec![TeForm]),
+        ("く", vec![], vec![Adverbial]),
+        ("かったら", vec![], vec![Conditional]),
+        ("ければ", vec![], vec![ProvisionalConditional]),
+        ("かろう", vec![], vec![Volitional]),
+        ("かったです", vec![], vec![Polite, Past]),
+        ("くありません", vec![], vec![Polite, Negative]),
+        ("くないです", vec![], vec![Polite, Negative]),
+        ("くありませんでした", vec![], vec![Polite, Negative, Past]),
+        ("さそう", vec![], vec![Appearance]),
+        ("さ", vec![], vec![Nominal]),
+        ("すぎる", vec![Ichidan], vec![Excessive]),
+    ]
+    .into_iter()
+    .flat_map(|(inflected, classes_in, forms)| {
+        // いい conjugates from the stem よ (adj-ix)
+        [
+            DeinflectionRule::new(inflected, "い", &classes_in, IAdjective, &forms),
+            DeinflectionRule::new(
+                &["よ", inflected].concat(),
+                "いい",
+                &classes_in,
+                IAdjective,
+                &forms,
+            ),
+        ]
+    })
+    .chain([
+        // よい and ない only look like this as よさそう and なさそう
+        DeinflectionRule::new("そう", "い", &[], IAdjective, &[Appearance])
+            .except(&["よい", "ない"]),
+        DeinflectionRule::new("いです", "い", &[], IAdjective, &[Polite]),
+        DeinflectionRule::new("ないで", "ない", &[], IAdjective, &[TeForm]),
+    ])
+    .collect()
+}
+
+fn na_adjective_rules() -> Vec<DeinflectionRule> {
+    use GrammaticalForm::*;
+    use WordClass::*;
+    [
+        ("だ", vec![], vec![Predicative]),
+        ("です", vec![], vec![Polite]),
+        ("じゃない", vec![IAdjective], vec![Negative]),
+        ("ではない", vec![IAdjective], vec![Negative]),
+        ("だった", vec![], vec![Past]),
+        ("でした", vec![], vec![Polite, Past]),
+        ("じゃありません", vec![], vec![Polite, Negative]),
+        ("ではありません", vec![], vec![Polite, Negative]),
+        ("じゃありませんでした", vec![], vec![Polite, Negative, Past]),
+        ("ではありませんでした", vec![], vec![Polite, Negative, Past]),
+        ("で", vec![], vec![TeForm]),
+        ("に", vec![], vec![Adverbial]),
+        ("な", vec![], vec![Attributive]),
+        ("なら", vec![], vec![ProvisionalConditional]),
+        ("だったら", vec![], vec![Conditional]),
+    ]
+    .into_iter()
+    .map(|(inflected, classes_in, forms)| {
+        DeinflectionRule::new(inflected, "", &classes_in, NaAdjective, &forms)
+    })
+    .collect()
+}
+
+fn masu_rules() -> Vec<DeinflectionRule> {
+    use GrammaticalForm::*;
+    [
+        ("ました", vec![Past]),
+        ("ません", vec![Negative]),
+        ("ませんでした", vec![Negative, Past]),
+        ("ましょう", vec![Volitional]),
+        ("まして", vec![TeForm]),
+        ("ましたら", vec![Conditional]),
+    ]
+    .into_iter()
+    .map(|(inflected, forms)| {
+        DeinflectionRule::new(inflected, "ます", &[], WordClass::Masu, &forms)
+    })
+    .collect()
+}
+
+fn keigo_rules() -> Vec<DeinflectionRule> {
+    use GrammaticalForm::*;
+    use WordClass::*;
+    let godan_stems = [
+        ("う", "い"),
+        ("く", "き"),
+        ("ぐ", "ぎ"),
+        ("す", "し"),
+        ("つ", "ち"),
+        ("ぬ", "に"),
+        ("ぶ", "び"),
+        ("む", "み"),
+        ("る", "り"),
+    ];
+    let regular_rules = godan_stems
+        .into_iter()
+        .map(|(u, i)| (i, u, Godan))
+        .chain(std::iter::once(("", "る", Ichidan)))
+        .flat_map(|(stem, base, class_out)| {
+            [
+                DeinflectionRule::new(
+                    &format!("{stem}になる"),
+                    base,
+                    &[Godan],
+                    class_out,
+                    &[Honorific],
+                ),
+                DeinflectionRule::new(&format!("{stem}する"), base, &[Suru], class_out, &[Humble]),
+            ]
+        })
+        .map(|rule| rule.with_prefix("お"));
+    // なさる, いらっしゃる, おっしゃる, くださる and ござる (v5aru)
+    let aru_rules = [
+        DeinflectionRule::new("い", "る", &[], GodanAru, &[MasuStem]),
+        DeinflectionRule::new("い", "る", &[], GodanAru, &[Imperative]),
+        DeinflectionRule::new("います", "る", &[Masu], GodanAru, &[Polite]),
+    ];
+    let suppletive_rules = SUPPLETIVE_VERBS.iter().flat_map(|verb| {
+        let classes_in: Vec<WordClass> = WordClass::ALL
+            .into_iter()
+            .filter(|class| class.matches_tag(verb.keigo_category))
+            .collect();
+        let class_out = WordClass::from_tag(verb.plain_category)
+            .expect("suppletive verbs only have verb categories");
+        // kana spellings lead to the kanji form too since readings like いる are
+        // ambiguous, except for する which suru-nouns still have to attach to
+        let plain_reading = match class_out {
+            Suru => verb.plain_reading,
+            _ => verb.plain,
+        };
+        [
+            (verb.keigo, verb.plain),
+            (verb.keigo_reading, plain_reading),
+        ]
+        .map(|(inflected, base)| {
+            DeinflectionRule::new(inflected, base, &classes_in, class_out, &[verb.form])
+        })
+    });
+    regular_rules
+        .chain(aru_rules)
+        .chain(suppletive_rules)
+        .collect()
+}
+
+fn auxiliary_rules() -> Vec<DeinflectionRule> {
+    use GrammaticalForm::*;
+    use WordClass::*;
+    [
+        ("いる", Ichidan, vec![Progressive]),
+        ("る", Ichidan, vec![Progressive, Short]),
+        ("しまう", Godan, vec![Completive]),
+        ("おく", Godan, vec![Preparatory]),
+        ("みる", Ichidan, vec![Attempt]),
+    ]
+    .into_iter()
+    .flat_map(|(auxiliary, class_in, forms)| {
+        ["て", "で"]
+            .map(|te| DeinflectionRule::new(&[te, auxiliary].concat(), te, &[class_in], Te, &forms))
+    })
+    .chain([
+        DeinflectionRule::new("ちゃう", "て", &[Godan], Te, &[Completive, Short]),
+        DeinflectionRule::new("じゃう", "で", &[Godan], Te, &[Completive, Short]),
+        DeinflectionRule::new("とく", "て", &[Godan], Te, &[Preparatory, Short]),
+        DeinflectionRule::new("どく", "で", &[Godan], Te, &[Preparatory, Short]),
+    ])
+    .collect()
+}
+
+/// Te-form rules reached through an auxiliary don't add another te-form to
+/// the explanation.
+fn te_form_continuations(rules: &[DeinflectionRule]) -> Vec<DeinflectionRule> {
+    rules
+        .iter()
+        .filter(|rule| rule.classes_in.is_empty() && rule.forms == [GrammaticalForm::TeForm])
+        .map(|rule| DeinflectionRule {
+            classes_in: vec![WordClass::Te],
+            forms: Vec::new(),
+            ..rule.clone()
+        })
+        .collect()
+}
+
+lazy_static! {
+    static ref DEINFLECTION_RULES: Vec<DeinflectionRule> = {
+        let mut rules: Vec<DeinflectionRule> = godan_rules()
+            .into_iter()
+            .chain(ichidan_rules())
+            .chain(kuru_rules())
+            .chain(suru_rules())
+            .chain(adjective_rules())
+            .chain(na_adjective_rules())
+            .chain(masu_rules())
+            .chain(keigo_rules())
+            .chain(auxiliary_rules())
+            .collect();
+        rules.extend(te_form_continuations(&rules));
+        rules
+    };
+    // indices of the rules by the last character of their ending
+    static ref RULES_BY_LAST_CHAR: HashMap<char, Vec<usize>> = {
+        let mut rules: HashMap<char, Vec<usize>> = HashMap::new();
+        for (index, rule) in DEINFLECTION_RULES.iter().enumerate() {
+            let last = rule.inflected.chars().last().unwrap_or(EMPTY_ENDING);
+            rules.entry(last).or_default().push(index);
+        }
+        rules
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        adjective_conjugation_utils::generate_all_adjective_conjugations,
+        verb_conjugation_utils::generate_all_verb_conjugations,
+    };
+    use GrammaticalForm::*;
+
+    fn find(term: &str, base: &str, class: WordClass) -> Option<Vec<GrammaticalForm>> {
+        deinflect(term)
+            .into_iter()
+            .find(|d| d.term == base && d.class == Some(class))
+            .map(|d| d.forms)
+    }
+
+    #[test]
+    fn test_may_be_inflected() {
+        assert!(may_be_inflected("食べました"));
+        assert!(may_be_inflected("たべ"));
+        assert!(may_be_inflected("まぜ"));
+        assert!(may_be_inflected("見"));
+        assert!(!may_be_inflected("ねこの"));
+        assert!(!may_be_inflected("これは"));
+        assert!(!may_be_inflected(""));
+    }
+
+    #[test]
+    fn test_deinflect_ichidan() {
+        assert_eq!(
+            find("食べさせられなかった", "食べる", WordClass::Ichidan).unwrap(),
+            vec![Causative, Passive, Negative, Past]
+        );
+        assert_eq!(
+            find("食べませんでした", "食べる", WordClass::Ichidan).unwrap(),
+            vec![Polite, Negative, Past]
+        );
+        assert_eq!(
+            find("食べないで", "食べる", WordClass::Ichidan).unwrap(),
+            vec![Negative, TeForm]
+        );
+        assert_eq!(
+            find("食べたくなかった", "食べる", WordClass::Ichidan).unwrap(),
+            vec![Desire, Negative, Past]
+        );
+    }
+
+    #[test]
+    fn test_deinflect_godan() {
+        assert_eq!(
+            find("書かれました", "書く", WordClass::Godan).unwrap(),
+            vec![Passive, Polite, Past]
+        );
+        assert_eq!(
+            find("読まなければ", "読む", WordClass::Godan).unwrap(),
+            vec![Negative, ProvisionalConditional]
+        );
+        assert_eq!(
+            find("泳いだ", "泳ぐ", WordClass::Godan).unwrap(),
+            vec![Past]
+        );
+        assert_eq!(
+            find("行った", "行く", WordClass::GodanIku).unwrap(),
+            vec![Past]
+        );
+        assert_eq!(
+            find("なかった", "ある", WordClass::Godan).unwrap(),
+            vec![Negative, Past]
+        );
+        assert!(find("行った", "行く", WordClass::Godan).is_none());
+    }
+
+    #[test]
+    fn test_deinflect_irregular() {
+        assert_eq!(
+            find("こなかった", "くる", WordClass::Kuru).unwrap(),
+            vec![Negative, Past]
+        );
+        assert_eq!(
+            find("来られる", "来る", WordClass::Kuru).unwrap(),
+            vec![Passive]
+        );
+        assert_eq!(
+            find("勉強させられた", "勉強する", WordClass::Suru).unwrap(),
+            vec![Causative, Passive, Past]
+        );
+        assert_eq!(
+            find("勉強しなかった", "勉強", WordClass::SuruNoun).unwrap(),
+            vec![Negative, Past]
+        );
+        assert_eq!(
+            find("高くなかったら", "高い", WordClass::IAdjective).unwrap(),
+            vec![Negative, Conditional]
+        );
+    }
+
+    #[test]
+    fn test_deinflect_i_adjective() {
+        assert_eq!(
+            find("高くなくて", "高い", WordClass::IAdjective).unwrap(),
+            vec![Negative, TeForm]
+        );
+        assert_eq!(
+            find("高すぎました", "高い", WordClass::IAdjective).unwrap(),
+            vec![Excessive, Polite, Past]
+        );
+        assert_eq!(
+            find("よくありませんでした", "いい", WordClass::IAdjective).unwrap(),
+            vec![Polite, Negative, Past]
+        );
+        assert_eq!(
+            find("よさそう", "いい", WordClass::IAdjective).unwrap(),
+            vec![Appearance]
+        );
+        assert_eq!(
+            find("なさそう", "ない", WordClass::IAdjective).unwrap(),
+            vec![Appearance]
+        );
+        assert_eq!(
+            find("高そう", "高い", WordClass::IAdjective).unwrap(),
+            vec![Appearance]
+        );
+        assert!(find("よそう", "よい", WordClass::IAdjective).is_none());
+        assert!(find("よそう", "いい", WordClass::IAdjective).is_none());
+    }
+
+    #[test]
+    fn test_deinflect_keigo() {
+        assert_eq!(
+            find("お読みになりました", "読む", WordClass::Godan).unwrap(),
+            vec![Honorific, Polite, Past]
+        );
+        assert_eq!(
+            find("お待ちしました", "待つ", WordClass::Godan).unwrap(),
+            vec![Humble, Polite, Past]
+        );
+        assert_eq!(
+            find("いらっしゃいました", "行く", WordClass::GodanIku).unwrap(),
+            vec![Honorific, Polite, Past]
+        );
+        assert_eq!(
+            find("召し上がって", "食べる", WordClass::Ichidan).unwrap(),
+            vec![Honorific, TeForm]
+        );
+        assert_eq!(
+            find("勉強いたします", "勉強", WordClass::SuruNoun).unwrap(),
+            vec![Humble, Polite]
+        );
+    }
+
+    #[test]
+    fn test_deinflect_auxiliary() {
+        assert_eq!(
+            find("食べている", "食べる", WordClass::Ichidan).unwrap(),
+            vec![Progressive]
+        );
+        assert_eq!(
+            find("読んでました", "読む", WordClass::Godan).unwrap(),
+            vec![Progressive, Short, Polite, Past]
+        );
+        assert_eq!(
+            find("食べちゃった", "食べる", WordClass::Ichidan).unwrap(),
+            vec![Completive, Short, Past]
+        );
+        assert_eq!(
+            find("飲んじゃう", "飲む", WordClass::Godan).unwrap(),
+            vec![Completive, Short]
+        );
+        assert_eq!(
+            find("買っておいた", "買う", WordClass::Godan).unwrap(),
+            vec![Preparatory, Past]
+        );
+        assert_eq!(
+            find("書いとく", "書く", WordClass::Godan).unwrap(),
+            vec![Preparatory, Short]
+        );
+        assert_eq!(
+            find("してみたい", "する", WordClass::Suru).unwrap(),
+            vec![Attempt, Desire]
+        );
+        assert_eq!(
+            find("食べたくて", "食べる", WordClass::Ichidan).unwrap(),
+            vec![Desire, TeForm]
+        );
+    }
+
+    #[test]
+    fn test_generated_form_order() {
+        let v1 = String::from("v1");
+        let adj = String::from("adj-i");
+        let taberu = generate_all_verb_conjugations("食べる", "たべる", vec![&v1])
+            .unwrap()
+            .unwrap();
+        let takai = generate_all_adjective_conjugations("高い", "たかい", vec![&adj]).unwrap();
+        for (conjugations, base, class, forms) in [
+            (&taberu, "食べる", WordClass::Ichidan, vec![Negative, Past]),
+            (
+                &taberu,
+                "食べる",
+                WordClass::Ichidan,
+                vec![Polite, Negative, Past],
+            ),
+            (
+                &taberu,
+                "食べる",
+                WordClass::Ichidan,
+                vec![Negative, Conditional],
+            ),
+            (
+                &taberu,
+                "食べる",
+                WordClass::Ichidan,
+                vec![Polite, Volitional],
+            ),
+            (
+                &takai,
+                "高い",
+                WordClass::IAdjective,
+                vec![Polite, Negative, Past],
+            ),
+        ] {
+            let conjugation = conjugations
+                .iter()
+                .find(|conjugation| conjugation.grammatical_forms == forms)
+                .unwrap();
+            assert_eq!(
+                find(&conjugation.kanji_form, base, class).unwrap(),
+                forms,
+                "{}",
+                conjugation.kanji_form
+            );
+        }
+    }
+
+    #[test]
+    fn test_deinflect_na_adjective() {
+        assert_eq!(
+            find("静かじゃなかった", "静か", WordClass::NaAdjective).unwrap(),
+            vec![Negative, Past]
+        );
+        assert_eq!(
+            find("綺麗でした", "綺麗", WordClass::NaAdjective).unwrap(),
+            vec![Polite, Past]
+        );
+        assert_eq!(
+            find("静かじゃなくて", "静か", WordClass::NaAdjective).unwrap(),
+            vec![Negative, TeForm]
+        );
+        assert_eq!(
+            find("静かではなくて", "静か", WordClass::NaAdjective).unwrap(),
+            vec![Negative, TeForm]
+        );
+    }
+}
@@ -55,4 +55,10 @@ pub fn radk_dict_paths() -> Vec<String> {
     vec!["resources/radicals/radkfilex.json".to_string()]
 }
 
+pub const EXAMPLE_SENTENCES_TSV: &str = "resources/sample_sentences.tsv";
+// local copies of https://tatoeba.org/downloads, used instead of the samples
+pub const TATOEBA_SENTENCES: &str = "resources/tatoeba/sentences.csv";
+pub const TATOEBA_LINKS: &str = "resources/tatoeba/links.csv";
+
 pub const DICTIONARY_ENTRIES: &str = "target/entrys.bin";
+pub const EXAMPLE_SENTENCES: &str = "target/sentences.bin";
@@ -0,0 +1,26 @@
+/// Width of a string in terminal columns, counting full-width characters twice.
+pub fn display_width(string: &str) -> usize {
+    string
+        .chars()
+        // the downstep mark is as narrow as ascii
+        .map(|c| if c.is_ascii() || c == 'ꜜ' { 1 } else { 2 })
+        .sum()
+}
+
+pub fn pad(string: &str, width: usize) -> String {
+    format!(
+        "{}{}",
+        string,
+        " ".repeat(width.saturating_sub(display_width(string)))
+    )
+}
+
+pub fn pad_centered(string: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(string));
+    format!(
+        "{}{}{}",
+        " ".repeat(padding / 2),
+        string,
+        " ".repeat(padding - padding / 2)
+    )
+}
@@ -0,0 +1,207 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    composite_dictionaries::bold, kana_utils::KANJI_CHARS, sentence::Sentence, DictionaryEntry,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExampleSentence {
+    pub id: u32,
+    pub text: String,
+    pub translations: Vec<String>,
+    /// Every parsed word with the id of the dictionary word it was read as
+    pub words: Vec<(String, Option<u32>)>,
+}
+
+impl ExampleSentence {
+    pub fn from(sentence: &Sentence) -> ExampleSentence {
+        ExampleSentence {
+            id: sentence.id(),
+            text: sentence.raw.clone(),
+            translations: sentence.translations.clone(),
+            words: sentence
+                .decomposition
+                .iter()
+                .map(|word| {
+                    let id = word
+                        .interpretations()
+                        .first()
+                        .filter(|entry| matches!(entry, DictionaryEntry::Word(_)))
+                        .and_then(|entry| entry.id());
+                    (word.word.clone(), id)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ExampleSentence {
+    /// The sentence with the word at `index` in bold.
+    pub fn highlight(&self, index: usize) -> String {
+        let mut highlighted = String::new();
+        let mut rest = self.text.as_str();
+        for (position, (word, _)) in self.words.iter().enumerate() {
+            let Some(start) = rest.find(word.as_str()) else {
+                continue;
+            };
+            highlighted.push_str(&rest[..start]);
+            match position == index {
+                true => highlighted.push_str(&bold(word)),
+                false => highlighted.push_str(word),
+            }
+            rest = &rest[start + word.len()..];
+        }
+        highlighted.push_str(rest);
+        highlighted
+    }
+}
+
+impl fmt::Display for ExampleSentence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)?;
+        if let Some(translation) = self.translations.first() {
+            write!(f, "\n    {}", translation)?;
+        }
+        Ok(())
+    }
+}
+
+/// Inverted index from words (by id) and kanji (by code point) to the example
+/// sentences they appear in, shortest sentences first.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ExampleSentences {
+    sentences: Vec<ExampleSentence>,
+    words: HashMap<u32, Vec<usize>>,
+    kanji: HashMap<u32, Vec<usize>>,
+}
+
+impl ExampleSentences {
+    pub fn create(sentences: &[Sentence]) -> ExampleSentences {
+        let sentences: Vec<ExampleSentence> = sentences
+            .iter()
+            .map(ExampleSentence::from)
+            .sorted_by_key(|sentence| (sentence.text.chars().count(), sentence.id))
+            .collect();
+        let mut words: HashMap<u32, Vec<usize>> = HashMap::new();
+        let mut kanji: HashMap<u32, Vec<usize>> = HashMap::new();
+        for (index, sentence) in sentences.iter().enumerate() {
+            for id in sentence.words.iter().filter_map(|(_, id)| *id).unique() {
+                words.entry(id).or_default().push(index);
+            }
+            for character in KANJI_CHARS
+                .find_iter(&sentence.text)
+                .filter_map(|kanji| kanji.as_str().chars().next())
+                .unique()
+            {
+                kanji.entry(character as u32).or_default().push(index);
+            }
+        }
+        ExampleSentences {
+            sentences,
+            words,
+            kanji,
+        }
+    }
+
+    pub fn for_entry(&self, entry: &DictionaryEntry) -> impl Iterator<Item = &ExampleSentence> {
+        let indices = match (entry, entry.id()) {
+            (DictionaryEntry::Word(_), Some(id)) => self.words.get(&id),
+            // kanji ids are their unicode code points
+            (DictionaryEntry::Kanji(_), Some(id)) => self.kanji.get(&id),
+            _ => None,
+        };
+        indices
+            .into_iter()
+            .flatten()
+            .map(|&index| &self.sentences[index])
+    }
+
+    /// Sentences containing one of the words in `ids` in any form, with the
+    /// position of the first matching word.
+    pub fn containing(&self, ids: &[u32]) -> Vec<(&ExampleSentence, usize)> {
+        ids.iter()
+            .filter_map(|id| self.words.get(id))
+            .flatten()
+            .copied()
+            .sorted()
+            .dedup()
+            .filter_map(|index| {
+                let sentence = &self.sentences[index];
+                let position = sentence
+                    .words
+                    .iter()
+                    .position(|(_, id)| id.is_some_and(|id| ids.contains(&id)))?;
+                Some((sentence, position))
+            })
+            .collect()
+    }
+
+    /// Sentences with a translation containing every word of `keywords`.
+    pub fn search_translations<'a>(
+        &'a self,
+        keywords: &str,
+    ) -> impl Iterator<Item = &'a ExampleSentence> {
+        let keywords: Vec<String> = english_words(keywords).collect();
+        self.sentences.iter().filter(move |sentence| {
+            sentence.translations.iter().any(|translation| {
+                let words: Vec<String> = english_words(translation).collect();
+                keywords.iter().all(|keyword| words.contains(keyword))
+            })
+        })
+    }
+}
+
+fn english_words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentence(id: u32, text: &str, translation: &str) -> Sentence<'static> {
+        let mut sentence = Sentence::create(text.to_string(), Vec::new(), id);
+        sentence.translations = vec![translation.to_string()];
+        sentence
+    }
+
+    #[test]
+    fn test_search_translations() {
+        let examples = ExampleSentences::create(&[
+            sentence(1, "猫が好きです。", "I like cats."),
+            sentence(2, "猫が大好きです。", "I really like the cat."),
+            sentence(3, "カテゴリーを選んで。", "Choose a category."),
+        ]);
+        let ids = |query| {
+            examples
+                .search_translations(query)
+                .map(|sentence| sentence.id)
+                .collect::<Vec<u32>>()
+        };
+        assert_eq!(ids("cat"), vec![2]);
+        assert_eq!(ids("Like"), vec![1, 2]);
+        assert_eq!(ids("like cats"), vec![1]);
+        assert!(ids("dog").is_empty());
+    }
+
+    #[test]
+    fn test_highlight() {
+        let sentence = ExampleSentence {
+            id: 1,
+            text: "猫が、好きです。".to_string(),
+            translations: Vec::new(),
+            words: ["猫", "が", "好き", "です"]
+                .iter()
+                .map(|word| (word.to_string(), None))
+                .collect(),
+        };
+        assert_eq!(
+            sentence.highlight(2),
+            format!("猫が、{}です。", bold("好き"))
+        );
+    }
+}
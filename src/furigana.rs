@@ -0,0 +1,389 @@
+use anyhow::{anyhow, Error, Result};
+use std::{fmt, str::FromStr};
+
+use crate::{
+    composite_dictionaries::Word,
+    display::{display_width, pad_centered},
+    kana_utils::{katakana_to_hiragana, KANJI_CHARS},
+    sentence::Sentence,
+    Dictionary, DictionaryEntry,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuriganaSegment {
+    pub text: String,
+    pub reading: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Furigana {
+    pub segments: Vec<FuriganaSegment>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FuriganaFormat {
+    Ruby,
+    Anki,
+    Terminal,
+}
+
+impl FromStr for FuriganaFormat {
+    type Err = Error;
+
+    fn from_str(format: &str) -> Result<Self> {
+        match format {
+            "ruby" | "html" => Ok(FuriganaFormat::Ruby),
+            "anki" => Ok(FuriganaFormat::Anki),
+            "terminal" => Ok(FuriganaFormat::Terminal),
+            _ => Err(anyhow!(
+                "Unknown furigana format '{}', expected ruby, anki or terminal",
+                format
+            )),
+        }
+    }
+}
+
+// ヶ is read like a kanji in words such as 関ヶ原
+fn is_kanji(c: char) -> bool {
+    c == 'ヶ' || KANJI_CHARS.is_match(c.encode_utf8(&mut [0; 4]))
+}
+
+/// Readings of a kanji as they appear inside words, e.g. た for た.べる
+pub fn normalize_kanji_reading(reading: &str) -> String {
+    let reading = reading.split('.').next().unwrap_or_default();
+    katakana_to_hiragana(reading.trim_matches('-'))
+}
+
+/// Rendaku and gemination turn 国 (こく) into ごく or こっ in compounds.
+fn reading_variants(reading: &str) -> Vec<String> {
+    let mut variants = vec![reading.to_string()];
+    let mut chars: Vec<char> = reading.chars().collect();
+    let Some(&first) = chars.first() else {
+        return variants;
+    };
+    let offset = first as u32;
+    let voiced: Vec<u32> = match first {
+        'か'..='ぢ' if (offset - 'か' as u32).is_multiple_of(2) => vec![1],
+        'つ' | 'て' | 'と' => vec![1],
+        'は'..='ほ' if (offset - 'は' as u32).is_multiple_of(3) => vec![1, 2],
+        _ => Vec::new(),
+    };
+    for shift in voiced {
+        if let Some(c) = char::from_u32(offset + shift) {
+            variants.push(
+                std::iter::once(c)
+                    .chain(chars[1..].iter().copied())
+                    .collect(),
+            );
+        }
+    }
+    if chars.len() > 1 && matches!(chars.last(), Some('つ' | 'く' | 'ち' | 'き')) {
+        chars.pop();
+        let stem: String = chars.into_iter().collect();
+        variants.push(format!("{}っ", stem));
+    }
+    variants
+}
+
+/// The reading out of `readings` that `variant` is a rendaku or gemination of.
+pub fn base_reading<'r>(readings: &'r [String], variant: &str) -> Option<&'r String> {
+    readings
+        .iter()
+        .find(|reading| reading_variants(reading).iter().any(|v| v == variant))
+}
+
+/// Splits the reading of a kanji run between its kanji using their known
+/// readings, `None` if the run is read as a whole (e.g. 今日).
+fn split_kanji_run(
+    kanji: &[char],
+    reading: &str,
+    kanji_readings: &dyn Fn(char) -> Vec<String>,
+    previous: Option<char>,
+) -> Option<Vec<String>> {
+    let Some((&first, rest)) = kanji.split_first() else {
+        return reading.is_empty().then(Vec::new);
+    };
+    let readings = match first {
+        '々' => kanji_readings(previous?),
+        _ => kanji_readings(first),
+    };
+    readings
+        .iter()
+        .flat_map(|reading| reading_variants(reading))
+        .filter(|variant| !variant.is_empty() && reading.starts_with(variant.as_str()))
+        .find_map(|variant| {
+            let mut split =
+                split_kanji_run(rest, &reading[variant.len()..], kanji_readings, Some(first))?;
+            split.insert(0, variant);
+            Some(split)
+        })
+}
+
+struct Alignment {
+    segments: Vec<FuriganaSegment>,
+    score: usize,
+}
+
+fn align_runs(
+    runs: &[(String, bool)],
+    reading: &str,
+    kanji_readings: &dyn Fn(char) -> Vec<String>,
+) -> Option<Alignment> {
+    let Some(((text, kanji), rest)) = runs.split_first() else {
+        return reading.is_empty().then(|| Alignment {
+            segments: Vec::new(),
+            score: 0,
+        });
+    };
+    if !kanji {
+        let mut alignment = align_runs(
+            rest,
+            reading.strip_prefix(katakana_to_hiragana(text).as_str())?,
+            kanji_readings,
+        )?;
+        alignment.segments.insert(
+            0,
+            FuriganaSegment {
+                text: text.clone(),
+                reading: None,
+            },
+        );
+        return Some(alignment);
+    }
+    let chars: Vec<char> = text.chars().collect();
+    reading
+        .char_indices()
+        .map(|(index, c)| index + c.len_utf8())
+        .skip(chars.len() - 1)
+        .filter_map(|end| {
+            let mut alignment = align_runs(rest, &reading[end..], kanji_readings)?;
+            let run_reading = &reading[..end];
+            match split_kanji_run(&chars, run_reading, kanji_readings, None) {
+                Some(split) => {
+                    let segments =
+                        chars
+                            .iter()
+                            .zip(split)
+                            .map(|(kanji, reading)| FuriganaSegment {
+                                text: kanji.to_string(),
+                                reading: Some(reading),
+                            });
+                    alignment.segments.splice(0..0, segments);
+                    alignment.score += 1;
+                }
+                None => alignment.segments.insert(
+                    0,
+                    FuriganaSegment {
+                        text: text.clone(),
+                        reading: Some(run_reading.to_string()),
+                    },
+                ),
+            }
+            Some(alignment)
+        })
+        .max_by_key(|alignment| alignment.score)
+}
+
+impl Furigana {
+    /// Aligns `reading` with the kanji runs of `text`, using `kanji_readings`
+    /// to pick between alignments and to split runs into single kanji.
+    pub fn align_with(
+        text: &str,
+        reading: &str,
+        kanji_readings: &dyn Fn(char) -> Vec<String>,
+    ) -> Furigana {
+        let runs: Vec<(String, bool)> =
+            text.chars()
+                .fold(Vec::new(), |mut runs: Vec<(String, bool)>, c| {
+                    match runs.last_mut() {
+                        Some((run, kanji)) if *kanji == is_kanji(c) => run.push(c),
+                        _ => runs.push((c.to_string(), is_kanji(c))),
+                    }
+                    runs
+                });
+        let segments = match runs.iter().any(|(_, kanji)| *kanji) {
+            true => align_runs(&runs, &katakana_to_hiragana(reading), kanji_readings)
+                .map(|alignment| alignment.segments),
+            false => None,
+        };
+        Furigana {
+            segments: segments.unwrap_or_else(|| {
+                vec![FuriganaSegment {
+                    text: text.to_string(),
+                    reading: (text != reading && runs.iter().any(|(_, kanji)| *kanji))
+                        .then(|| reading.to_string()),
+                }]
+            }),
+        }
+    }
+
+    pub fn align(dict: &Dictionary, text: &str, reading: &str) -> Furigana {
+        Furigana::align_with(text, reading, &|kanji| {
+            dict.get_kanji(&kanji.to_string())
+                .into_iter()
+                .flat_map(|entry| entry.readings().map(normalize_kanji_reading))
+                .collect()
+        })
+    }
+
+    pub fn from_sentence(dict: &Dictionary, sentence: &Sentence) -> Furigana {
+        let mut segments: Vec<FuriganaSegment> = Vec::new();
+        let mut rest = sentence.raw.as_str();
+        for word in &sentence.decomposition {
+            let Some(start) = rest.find(&word.word) else {
+                continue;
+            };
+            if start > 0 {
+                segments.push(FuriganaSegment {
+                    text: rest[..start].to_string(),
+                    reading: None,
+                });
+            }
+            let reading = word
+                .interpretations()
+                .first()
+                .and_then(|entry| reading_of(entry, &word.word));
+            match reading {
+                Some(reading) => {
+                    segments.extend(Furigana::align(dict, &word.word, &reading).segments)
+                }
+                None => segments.push(FuriganaSegment {
+                    text: word.word.clone(),
+                    reading: None,
+                }),
+            }
+            rest = &rest[start + word.word.len()..];
+        }
+        if !rest.is_empty() {
+            segments.push(FuriganaSegment {
+                text: rest.to_string(),
+                reading: None,
+            });
+        }
+        Furigana { segments }
+    }
+
+    pub fn to_ruby_html(&self) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match &segment.reading {
+                Some(reading) => format!("<ruby>{}<rt>{}</rt></ruby>", segment.text, reading),
+                None => segment.text.clone(),
+            })
+            .collect()
+    }
+
+    pub fn to_anki(&self) -> String {
+        self.segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| match &segment.reading {
+                // Anki attaches readings to everything back to the previous space
+                Some(reading) if index > 0 => format!(" {}[{}]", segment.text, reading),
+                Some(reading) => format!("{}[{}]", segment.text, reading),
+                None => segment.text.clone(),
+            })
+            .collect()
+    }
+
+    pub fn to_terminal(&self) -> String {
+        let mut readings = String::new();
+        let mut text = String::new();
+        for segment in self.segments.iter() {
+            let text_width = display_width(&segment.text);
+            let reading = segment.reading.as_deref().unwrap_or_default();
+            let width = text_width.max(display_width(reading));
+            readings.push_str(&pad_centered(reading, width));
+            text.push_str(&pad_centered(&segment.text, width));
+        }
+        format!("{}\n{}", readings.trim_end(), text.trim_end())
+    }
+
+    pub fn render(&self, format: FuriganaFormat) -> String {
+        match format {
+            FuriganaFormat::Ruby => self.to_ruby_html(),
+            FuriganaFormat::Anki => self.to_anki(),
+            FuriganaFormat::Terminal => self.to_terminal(),
+        }
+    }
+}
+
+impl fmt::Display for Furigana {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_terminal())
+    }
+}
+
+/// Reading of `surface` when it is `entry` or one of its conjugations.
+fn reading_of(entry: &DictionaryEntry, surface: &str) -> Option<String> {
+    match entry {
+        DictionaryEntry::Word(word) if word.vocabulary == surface => Some(word.reading.clone()),
+        DictionaryEntry::Word(word) => conjugated_reading(word, surface),
+        DictionaryEntry::Name(name) if name.name == surface => Some(name.reading.clone()),
+        _ => None,
+    }
+}
+
+fn conjugated_reading(word: &Word, surface: &str) -> Option<String> {
+    if let Some(conjugation) = word
+        .conjugations()
+        .ok()?
+        .into_iter()
+        .find(|conjugation| conjugation.kanji_form == surface)
+    {
+        return Some(conjugation.kana_form);
+    }
+    // surfaces matched through their reading are already written in kana
+    (word.reading == surface).then(|| surface.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn readings(kanji: char) -> Vec<String> {
+        let readings: &[&str] = match kanji {
+            '食' => &["しょく", "た.べる", "く.う"],
+            '物' => &["ぶつ", "もつ", "もの"],
+            '東' => &["とう", "ひがし"],
+            '京' => &["きょう", "けい"],
+            '学' => &["がく", "まな.ぶ"],
+            '校' => &["こう"],
+            '人' => &["じん", "にん", "ひと"],
+            _ => &[],
+        };
+        readings
+            .iter()
+            .map(|reading| normalize_kanji_reading(reading))
+            .collect()
+    }
+
+    fn align(text: &str, reading: &str) -> Furigana {
+        Furigana::align_with(text, reading, &readings)
+    }
+
+    #[test]
+    fn test_align_okurigana() {
+        assert_eq!(align("食べ物", "たべもの").to_anki(), "食[た]べ 物[もの]");
+        assert_eq!(
+            align("食べ物", "たべもの").to_ruby_html(),
+            "<ruby>食<rt>た</rt></ruby>べ<ruby>物<rt>もの</rt></ruby>"
+        );
+    }
+
+    #[test]
+    fn test_align_compounds() {
+        assert_eq!(align("東京", "とうきょう").to_anki(), "東[とう] 京[きょう]");
+        assert_eq!(align("学校", "がっこう").to_anki(), "学[がっ] 校[こう]");
+        assert_eq!(align("人々", "ひとびと").to_anki(), "人[ひと] 々[びと]");
+        assert_eq!(align("今日", "きょう").to_anki(), "今日[きょう]");
+    }
+
+    #[test]
+    fn test_terminal_overlay() {
+        assert_eq!(
+            align("食べ物", "たべもの").to_terminal(),
+            "た  もの\n食べ 物"
+        );
+    }
+}
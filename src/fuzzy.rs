@@ -0,0 +1,222 @@
+use std::{cmp::Reverse, collections::HashMap, sync::OnceLock};
+
+use itertools::Itertools;
+
+use crate::{meaning_index::words, DictionaryEntry};
+
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Number of single character insertions, deletions, substitutions and swaps
+/// of neighbours that turn `a` into `b`, so recieve is one typo away from
+/// receive.
+pub fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut before_previous: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current: Vec<usize> = vec![0; b.len() + 1];
+    for i in 0..a.len() {
+        current[0] = i + 1;
+        for j in 0..b.len() {
+            let substitution = previous[j] + usize::from(a[i] != b[j]);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+            if i > 0 && j > 0 && a[i] == b[j - 1] && a[i - 1] == b[j] {
+                current[j + 1] = current[j + 1].min(before_previous[j - 1] + 1);
+            }
+        }
+        std::mem::swap(&mut before_previous, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Typos tolerated in a query, longer queries may have more.
+fn max_distance(query: &str) -> usize {
+    match query.chars().count() {
+        0..=4 => 1,
+        _ => 2,
+    }
+}
+
+struct Node {
+    term: String,
+    chars: Vec<char>,
+    weight: u32,
+    // children by their distance to this node
+    children: Vec<(usize, usize)>,
+}
+
+/// Kana readings, and romaji and english meaning words, weighted by how
+/// frequent the words using them are. Each gets a BK-tree once a query in
+/// its script needs one.
+pub struct FuzzyIndex {
+    latin: (HashMap<String, u32>, OnceLock<BkTree>),
+    kana: (HashMap<String, u32>, OnceLock<BkTree>),
+}
+
+impl FuzzyIndex {
+    /// Terms of every entry but names, which would drown out everything else.
+    pub fn create(entries: &[DictionaryEntry]) -> FuzzyIndex {
+        let mut weights: HashMap<String, u32> = HashMap::new();
+        for entry in entries {
+            let (terms, weight): (Vec<String>, u32) = match entry {
+                DictionaryEntry::Word(word) => (
+                    [word.reading.as_str(), word.romaji()]
+                        .into_iter()
+                        .map(String::from)
+                        .chain(entry.meanings().flat_map(|meaning| {
+                            words(meaning).map(|(_, word)| word).collect::<Vec<_>>()
+                        }))
+                        .collect(),
+                    word.frequency().unwrap_or_default().saturating_add(1),
+                ),
+                DictionaryEntry::Kanji(_) => (
+                    entry
+                        .meanings()
+                        .flat_map(|meaning| {
+                            words(meaning).map(|(_, word)| word).collect::<Vec<_>>()
+                        })
+                        .collect(),
+                    1,
+                ),
+                _ => continue,
+            };
+            for term in terms.into_iter().filter(|term| !term.is_empty()) {
+                let total = weights.entry(term).or_default();
+                *total = total.saturating_add(weight);
+            }
+        }
+        FuzzyIndex::from_terms(weights)
+    }
+
+    pub fn from_terms(weights: HashMap<String, u32>) -> FuzzyIndex {
+        // a typo never turns romaji into kana, so each script gets its own tree
+        let (latin, kana): (HashMap<String, u32>, HashMap<String, u32>) =
+            weights.into_iter().partition(|(term, _)| term.is_ascii());
+        FuzzyIndex {
+            latin: (latin, OnceLock::new()),
+            kana: (kana, OnceLock::new()),
+        }
+    }
+
+    /// Other terms a few typos away from `query`, closest and most used first.
+    pub fn suggest(&self, query: &str) -> Vec<String> {
+        let (weights, tree) = match query.is_ascii() {
+            true => &self.latin,
+            false => &self.kana,
+        };
+        tree.get_or_init(|| BkTree::from(weights))
+            .find(query, max_distance(query))
+            .into_iter()
+            .filter(|(distance, _)| *distance > 0)
+            .sorted_by_key(|(distance, node)| (*distance, Reverse(node.weight), &node.term))
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, node)| node.term.clone())
+            .collect()
+    }
+}
+
+struct BkTree {
+    nodes: Vec<Node>,
+}
+
+impl BkTree {
+    fn from(weights: &HashMap<String, u32>) -> BkTree {
+        let mut tree = BkTree { nodes: Vec::new() };
+        // sorted terms would grow long chains of shared prefixes
+        for (term, weight) in weights {
+            tree.insert(term.clone(), *weight);
+        }
+        tree
+    }
+
+    fn insert(&mut self, term: String, weight: u32) {
+        let chars: Vec<char> = term.chars().collect();
+        let new = self.nodes.len();
+        let mut current = 0;
+        while current < self.nodes.len() {
+            let distance = edit_distance(&chars, &self.nodes[current].chars);
+            match self.nodes[current]
+                .children
+                .iter()
+                .find(|(child_distance, _)| *child_distance == distance)
+            {
+                Some(&(_, child)) => current = child,
+                None => {
+                    self.nodes[current].children.push((distance, new));
+                    break;
+                }
+            }
+        }
+        self.nodes.push(Node {
+            term,
+            chars,
+            weight,
+            children: Vec::new(),
+        });
+    }
+
+    /// Terms within `max_distance` of `query` with their distance and weight.
+    fn find(&self, query: &str, max_distance: usize) -> Vec<(usize, &Node)> {
+        let query: Vec<char> = query.chars().collect();
+        let mut found: Vec<(usize, &Node)> = Vec::new();
+        let mut stack: Vec<usize> = match self.nodes.is_empty() {
+            true => Vec::new(),
+            false => vec![0],
+        };
+        while let Some(current) = stack.pop() {
+            let node = &self.nodes[current];
+            let distance = edit_distance(&query, &node.chars);
+            if distance <= max_distance {
+                found.push((distance, node));
+            }
+            // the triangle inequality rules out every other subtree, swaps
+            // bend it slightly so a rare match may be missed
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|(child_distance, _)| child_distance.abs_diff(distance) <= max_distance)
+                    .map(|(_, child)| *child),
+            );
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance(a: &str, b: &str) -> usize {
+        edit_distance(
+            &a.chars().collect::<Vec<char>>(),
+            &b.chars().collect::<Vec<char>>(),
+        )
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(distance("taberu", "taberu"), 0);
+        assert_eq!(distance("tabreru", "taberu"), 1);
+        assert_eq!(distance("recieve", "receive"), 1);
+        assert_eq!(distance("たべる", "たべた"), 1);
+        assert_eq!(distance("", "dog"), 3);
+        assert_eq!(distance("dgo", "dog"), 1);
+    }
+
+    #[test]
+    fn test_suggest() {
+        let index = FuzzyIndex::from_terms(HashMap::from(
+            [
+                ("taberu", 5),
+                ("tabiru", 1),
+                ("receive", 3),
+                ("deceive", 1),
+                ("dog", 9),
+            ]
+            .map(|(term, weight)| (term.to_string(), weight)),
+        ));
+        assert_eq!(index.suggest("tabreru"), vec!["taberu", "tabiru"]);
+        assert_eq!(index.suggest("recieve"), vec!["receive", "deceive"]);
+        assert_eq!(index.suggest("dgo"), vec!["dog"]);
+        assert!(index.suggest("dog").is_empty());
+    }
+}
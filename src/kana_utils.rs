@@ -102,17 +102,26 @@ pub fn romaji_to_katakana(word: &str) -> Result<String> {
 
 #[allow(dead_code)]
 pub fn romaji_to_hiragana(word: &str) -> Result<String> {
-    romaji_to_katakana(word)
-        .map(|katakana| katakana_to_hiragana(&katakana).expect("Should be valid hiragana"))
+    romaji_to_katakana(word).map(|katakana| katakana_to_hiragana(&katakana))
 }
 
-#[allow(dead_code)]
-pub fn katakana_to_hiragana(word: &str) -> Result<String> {
-    if !word.chars().all(|c| HIRAGANA_CHARS.contains(&(c as u16))) {
-        return Err(NotConvertibleError::new(word).into());
-    }
-    let as_u16: Vec<u16> = word.chars().map(|char| char as u16 - 0x60).collect();
-    Ok(String::from_utf16(&as_u16).expect("Should be valid utf8"))
+/// Katakana of a word turned into hiragana, everything else is kept.
+pub fn katakana_to_hiragana(word: &str) -> String {
+    word.chars()
+        .map(|c| match c {
+            'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            c => c,
+        })
+        .collect()
+}
+
+pub fn hiragana_to_katakana(word: &str) -> String {
+    word.chars()
+        .map(|c| match c {
+            'ぁ'..='ゖ' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+            c => c,
+        })
+        .collect()
 }
 
 #[derive(Debug)]
@@ -190,17 +199,18 @@ mod tests {
 
     #[test]
     fn test_katakana_to_hiragana() {
-        assert_eq!(katakana_to_hiragana("サヨナラ").unwrap(), "さよなら");
-        assert_eq!(katakana_to_hiragana("アリガトウ").unwrap(), "ありがとう");
-        assert_eq!(katakana_to_hiragana("オハヨウ").unwrap(), "おはよう");
-        assert_eq!(katakana_to_hiragana("ニホン").unwrap(), "にほん");
-        assert_eq!(katakana_to_hiragana("キョウ").unwrap(), "きょう");
-        assert_eq!(katakana_to_hiragana("ガッコウ").unwrap(), "がっこう");
-        assert_eq!(katakana_to_hiragana("リョコウ").unwrap(), "りょこう");
-        assert_eq!(katakana_to_hiragana("シャシン").unwrap(), "しゃしん");
-        assert_eq!(katakana_to_hiragana("チカテツ").unwrap(), "ちかてつ");
-        assert_eq!(katakana_to_hiragana("ジュウショ").unwrap(), "じゅうしょ");
-        assert_eq!(katakana_to_hiragana("フジ").unwrap(), "ふじ");
+        assert_eq!(katakana_to_hiragana("サヨナラ"), "さよなら");
+        assert_eq!(katakana_to_hiragana("アリガトウ"), "ありがとう");
+        assert_eq!(katakana_to_hiragana("オハヨウ"), "おはよう");
+        assert_eq!(katakana_to_hiragana("ニホン"), "にほん");
+        assert_eq!(katakana_to_hiragana("キョウ"), "きょう");
+        assert_eq!(katakana_to_hiragana("ガッコウ"), "がっこう");
+        assert_eq!(katakana_to_hiragana("リョコウ"), "りょこう");
+        assert_eq!(katakana_to_hiragana("シャシン"), "しゃしん");
+        assert_eq!(katakana_to_hiragana("チカテツ"), "ちかてつ");
+        assert_eq!(katakana_to_hiragana("ジュウショ"), "じゅうしょ");
+        assert_eq!(katakana_to_hiragana("フジ"), "ふじ");
+        assert_eq!(katakana_to_hiragana("食べルーム"), "食べるーむ");
     }
 }
 
@@ -633,8 +643,6 @@ const SOUKUN_CHARS: [u8; 19] = [
     98, 99, 100, 102, 103, 104, 106, 107, 108, 109, 112, 114, 115, 116, 118, 119, 120, 121, 122,
 ];
 
-const HIRAGANA_CHARS: std::ops::RangeInclusive<u16> = 0x3041..=0x30fe;
-
 #[allow(dead_code)]
 const KATAKANA_CHARS: std::ops::RangeInclusive<u16> = 0x30A0..=0x30FF;
 
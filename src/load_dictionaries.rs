@@ -2,7 +2,7 @@ use anyhow::Result;
 use serde::Deserialize;
 use std::{error::Error, fmt, path::Path};
 
-pub fn load_vec_from_bin<D: for<'a> Deserialize<'a>>(path: &str) -> Result<Vec<D>> {
+pub fn load_from_bin<D: for<'a> Deserialize<'a>>(path: &str) -> Result<D> {
     if Path::new(path).exists() {
         let encoded = std::fs::read(path).unwrap_or_else(|e| {
             panic!(
@@ -10,7 +10,7 @@ pub fn load_vec_from_bin<D: for<'a> Deserialize<'a>>(path: &str) -> Result<Vec<D
                 path, e
             )
         });
-        let decoded: Vec<D> = bincode::deserialize(&encoded).unwrap_or_else(|e| {
+        let decoded: D = bincode::deserialize(&encoded).unwrap_or_else(|e| {
             panic!("Failed to decode binary dictionary: {}, reason {}", path, e)
         });
         Ok(decoded)
@@ -19,6 +19,12 @@ pub fn load_vec_from_bin<D: for<'a> Deserialize<'a>>(path: &str) -> Result<Vec<D
     }
 }
 
+/// Like `load_from_bin`, but empty for binaries that builds from before they
+/// were added don't have yet.
+pub fn load_from_bin_or_default<D: for<'a> Deserialize<'a> + Default>(path: &str) -> D {
+    load_from_bin(path).unwrap_or_default()
+}
+
 #[derive(Debug)]
 struct NoBuildError;
 
@@ -29,3 +35,17 @@ impl fmt::Display for NoBuildError {
         write!(f, "Dictionaries have not been build yet.\nPlease run the program again with flag '-r' enabled")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_sentences::ExampleSentences;
+
+    #[test]
+    fn test_missing_binary() {
+        let path = "target/no_such_binary.bin";
+        assert!(load_from_bin::<ExampleSentences>(path).is_err());
+        let examples: ExampleSentences = load_from_bin_or_default(path);
+        assert!(examples.containing(&[1]).is_empty());
+    }
+}
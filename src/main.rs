@@ -1,39 +1,328 @@
-#![feature(hash_extract_if)]
-use crate::load_dictionaries::load_vec_from_bin;
+use crate::load_dictionaries::{load_from_bin, load_from_bin_or_default};
 use anyhow::Result;
 use build_dictionaries::build_composite_dicts;
 use composite_dictionaries::*;
-use dictionary_paths::DICTIONARY_ENTRIES;
-use parse_example_sentences::parse_example_sentences_from_tsv;
+use conjugation_table::ConjugationTable;
+use dictionary_paths::{DICTIONARY_ENTRIES, EXAMPLE_SENTENCES};
+use example_sentences::{ExampleSentence, ExampleSentences};
+use furigana::{Furigana, FuriganaFormat};
+use itertools::Itertools;
+use kana_utils::KANJI_CHARS;
+use parse_example_sentences::parse_sentence;
 use query::*;
+use query_language::Filter;
+use radical_search::{RadicalQuery, RadicalSearch};
 use std::io::{self, Write};
-use structopt::StructOpt;
+use structopt::{clap::AppSettings, StructOpt};
 
 mod adjective_conjugation_utils;
 mod basic_dictionaries;
 mod build_dictionaries;
 mod composite_dictionaries;
+mod conjugation_table;
+mod deinflection;
 mod dictionary_paths;
+mod display;
+mod example_sentences;
+mod furigana;
+mod fuzzy;
 mod kana_utils;
 mod load_dictionaries;
+mod meaning_index;
 mod parse_example_sentences;
+mod pitch_accent;
 mod query;
+mod query_language;
+mod radical_search;
+mod ranking;
 mod sentence;
+mod similar_kanji;
+#[cfg(test)]
+mod test_utils;
+mod tokenizer;
 mod verb_conjugation_utils;
+mod wildcard;
+
+const EXAMPLE_COUNT: usize = 3;
+const PAGE_SIZE: usize = 20;
 
 #[derive(StructOpt)]
 #[structopt(
     name = "Kanji Dictionary",
     about = "A Command Line Kanji Dictionary",
-    author = "Nils Fischer"
+    author = "Nils Fischer",
+    // lets queries exclude with filters like -#name
+    setting = AppSettings::AllowLeadingHyphen
 )]
 struct Opt {
     #[structopt(short, long)]
     rebuild: bool,
 
+    /// Prints how relevant each result is to the query
+    #[structopt(short, long)]
+    verbose: bool,
+
+    /// Which page of results to print, 20 results each
+    #[structopt(long, default_value = "1")]
+    page: usize,
+
+    #[structopt(subcommand)]
+    command: Option<Command>,
+
+    /// A query, e.g. `犬 #kanji` or `dog -#name`
     args: Vec<String>,
 }
 
+#[derive(StructOpt)]
+enum Command {
+    /// Prints every conjugated form of a verb or adjective
+    Conjugate {
+        word: String,
+
+        #[structopt(long)]
+        json: bool,
+    },
+    /// Prints a word or sentence with furigana as ruby html, anki or terminal text
+    Furigana {
+        text: String,
+
+        #[structopt(long, default_value = "terminal")]
+        format: FuriganaFormat,
+    },
+    /// Prints example sentences containing a word in any of its forms, or
+    /// whose english translation contains the query
+    Sentences {
+        query: String,
+
+        /// Skips sentences longer than this many characters
+        #[structopt(long)]
+        max_length: Option<usize>,
+
+        /// Skips sentences with other words using kanji above this JLPT level
+        #[structopt(long)]
+        jlpt: Option<u8>,
+    },
+    /// Finds kanji containing every given radical, e.g. `radicals 口 木 +strokes:7`
+    Radicals { query: Vec<String> },
+    /// Prints the numbered pitch accents of a word, or one of them as an svg graph
+    Pitch {
+        word: String,
+
+        #[structopt(long)]
+        svg: bool,
+
+        /// Which accent to print as svg
+        #[structopt(long, default_value = "1")]
+        index: usize,
+    },
+    /// Lists kanji that look like the given kanji
+    Similar { kanji: String },
+}
+
+fn print_conjugations(dict: &Dictionary, word: &str, json: bool) -> Result<()> {
+    let mut tables: Vec<ConjugationTable> = Vec::new();
+    for result in dict.query_with_filters(word, &[Filter::Kind("Word")]) {
+        if matches!(
+            result.match_reason,
+            MatchReason::Meaning(_) | MatchReason::Tag(_)
+        ) {
+            continue;
+        }
+        if let DictionaryEntry::Word(entry) = result.entry {
+            match ConjugationTable::from_word(entry) {
+                Ok(Some(table)) => tables.push(table),
+                Ok(None) => {}
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+    }
+    if json {
+        println!("{}", serde_json::to_string_pretty(&tables)?);
+    } else if tables.is_empty() {
+        println!("No conjugations found for {}", word);
+    } else {
+        for table in tables {
+            println!("{}", table);
+        }
+    }
+    Ok(())
+}
+
+fn print_sentences(
+    dict: &Dictionary,
+    examples: &ExampleSentences,
+    query: &str,
+    max_length: Option<usize>,
+    jlpt: Option<u8>,
+) -> Result<()> {
+    let ids: Vec<u32> = dict
+        .query_with_filters(query, &[Filter::Kind("Word")])
+        .into_iter()
+        .filter(|result| {
+            matches!(
+                result.match_reason,
+                MatchReason::Vocabulary(_)
+                    | MatchReason::Reading(_)
+                    | MatchReason::Conjugation { .. }
+            )
+        })
+        .filter_map(|result| result.entry.id())
+        .collect();
+    let sentences: Vec<(&ExampleSentence, usize)> = match ids.is_empty() {
+        true => examples
+            .search_translations(query)
+            .map(|sentence| (sentence, usize::MAX))
+            .collect(),
+        false => examples.containing(&ids),
+    };
+    let kanji_level = |kanji: &str| dict.get_kanji(kanji).and_then(|entry| entry.jlpt());
+    for (sentence, position) in sentences {
+        if max_length.is_some_and(|max_length| sentence.text.chars().count() > max_length) {
+            continue;
+        }
+        // lower levels are harder, kanji without a level are harder still
+        let too_hard = jlpt.is_some_and(|jlpt| {
+            sentence
+                .words
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| *index != position)
+                .flat_map(|(_, (word, _))| KANJI_CHARS.find_iter(word))
+                .any(|kanji| kanji_level(kanji.as_str()).is_none_or(|levels| *levels.end() < jlpt))
+        });
+        if too_hard {
+            continue;
+        }
+        println!("{}", sentence.highlight(position));
+        for translation in sentence.translations.iter() {
+            println!("    {}", translation);
+        }
+    }
+    Ok(())
+}
+
+fn print_pitch_accents(dict: &Dictionary, word: &str, svg: bool, index: usize) -> Result<()> {
+    let results = dict.query_with_filters(word, &[Filter::Kind("Word")]);
+    let accents: Vec<_> = results
+        .iter()
+        .filter(|result| {
+            matches!(
+                result.match_reason,
+                MatchReason::Vocabulary(_) | MatchReason::Reading(_)
+            )
+        })
+        .filter_map(|result| match result.entry {
+            DictionaryEntry::Word(entry) => Some(entry),
+            _ => None,
+        })
+        .flat_map(|entry| {
+            entry
+                .pitch_accents()
+                .map(move |(accent, pitch)| (entry, accent, pitch))
+        })
+        .collect();
+    // an svg file holds a single graph
+    if svg {
+        match index.checked_sub(1).and_then(|index| accents.get(index)) {
+            Some((_, accent, _)) => println!("{}", accent.to_svg()),
+            None => println!(
+                "No pitch accent {} for {}, found {}",
+                index,
+                word,
+                accents.len()
+            ),
+        }
+        return Ok(());
+    }
+    for (number, (entry, accent, pitch)) in accents.iter().enumerate() {
+        println!(
+            "{}. {} ({}) {} [{}]",
+            number + 1,
+            bold(&entry.vocabulary),
+            entry.reading,
+            accent.pattern(),
+            pitch.position()
+        );
+        println!("{}", accent);
+        let tags = pitch.tags().map(|tag| tag.description.clone()).join(", ");
+        if !tags.is_empty() {
+            println!("({})", tags);
+        }
+    }
+    Ok(())
+}
+
+fn print_similar_kanji(dict: &Dictionary, kanji: &str) -> Result<()> {
+    let Some(entry) = dict.get_kanji(kanji) else {
+        println!("No kanji found for {}", kanji);
+        return Ok(());
+    };
+    for similar in entry
+        .similar()
+        .filter_map(|similar| dict.get_kanji(similar))
+    {
+        println!(
+            "{} {}",
+            bold(similar.kanji()),
+            similar.meanings().take(3).join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn print_result(result: &QueryResult, examples: &ExampleSentences, verbose: bool) {
+    if verbose {
+        println!("{} {}", bold("Score:"), result.score);
+    }
+    print!("{}", result);
+    let sentences: Vec<&ExampleSentence> = examples
+        .for_entry(result.entry)
+        .take(EXAMPLE_COUNT)
+        .collect();
+    if !sentences.is_empty() {
+        println!("{}", bold("Examples:"));
+        for sentence in sentences {
+            println!("{}", sentence);
+        }
+    }
+    println!();
+}
+
+fn print_results(results: &[QueryResult], examples: &ExampleSentences, opt: &Opt) {
+    let pages = results.len().div_ceil(PAGE_SIZE);
+    let page = opt.page.clamp(1, pages.max(1));
+    for result in results.iter().skip((page - 1) * PAGE_SIZE).take(PAGE_SIZE) {
+        print_result(result, examples, opt.verbose);
+    }
+    if pages > 1 {
+        println!(
+            "Page {} of {} ({} results), use --page <n> for the others",
+            page,
+            pages,
+            results.len()
+        );
+    }
+}
+
+fn print_query(dict: &Dictionary, query: &str, examples: &ExampleSentences, opt: &Opt) {
+    let results = match dict.query(query) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+    if !results.is_empty() {
+        print_results(&results, examples, opt);
+        return;
+    }
+    let suggestions = dict.suggest(query);
+    if let Some(suggestion) = suggestions.first() {
+        println!("{} {}?\n", bold("Did you mean"), suggestions.join(", "));
+        print_results(&dict.query(suggestion).unwrap_or_default(), examples, opt);
+    }
+}
+
 fn read_input(input: &mut String) -> Result<Vec<&str>> {
     print!("Enter query: ");
 
@@ -52,36 +341,48 @@ fn main() -> Result<()> {
         }
     }
     // combined dict
-    let entries: Vec<DictionaryEntry> = load_vec_from_bin(DICTIONARY_ENTRIES)?;
+    let entries: Vec<DictionaryEntry> = load_from_bin(DICTIONARY_ENTRIES)?;
 
     // longest word is a name 42 characters long
     let dict: Dictionary = Dictionary::create(&entries);
-    let sentences = parse_example_sentences_from_tsv("resources/sample_sentences.tsv", &dict)?;
-    let defects: Vec<&str> = sentences
-        .iter()
-        .filter(|sentence| sentence.decomposition.is_none())
-        .map(|sentence| sentence.raw.as_ref())
-        .collect();
-    println!("{}", defects.len());
-    for defect in defects {
-        println!("{:#?}", defect);
+    // builds from before example sentences have none until they are rebuilt
+    let examples: ExampleSentences = load_from_bin_or_default(EXAMPLE_SENTENCES);
+    match opt.command {
+        Some(Command::Conjugate { word, json }) => return print_conjugations(&dict, &word, json),
+        Some(Command::Furigana { text, format }) => {
+            let sentence = parse_sentence(&dict, &text, 0);
+            println!(
+                "{}",
+                Furigana::from_sentence(&dict, &sentence).render(format)
+            );
+            return Ok(());
+        }
+        Some(Command::Sentences {
+            query,
+            max_length,
+            jlpt,
+        }) => return print_sentences(&dict, &examples, &query, max_length, jlpt),
+        Some(Command::Radicals { query }) => {
+            print!(
+                "{}",
+                RadicalSearch::search(&dict, &RadicalQuery::parse(&query)?)?
+            );
+            return Ok(());
+        }
+        Some(Command::Pitch { word, svg, index }) => {
+            return print_pitch_accents(&dict, &word, svg, index)
+        }
+        Some(Command::Similar { kanji }) => return print_similar_kanji(&dict, &kanji),
+        None => {}
     }
-
     if opt.args.is_empty() {
         loop {
             let empty_str = &mut String::new();
             let query: String = read_input(empty_str)?.join(" ");
-            for result in dict.query(&query) {
-                println!("{}", result)
-            }
+            print_query(&dict, &query, &examples, &opt);
         }
     } else {
-        for arg in opt.args {
-            let query = arg.as_str();
-            for result in dict.query(query) {
-                println!("{}", result)
-            }
-        }
+        print_query(&dict, &opt.args.join(" "), &examples, &opt);
     }
     Ok(())
 }
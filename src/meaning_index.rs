@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use crate::DictionaryEntry;
+
+// besides the usual stop words, JMdict glosses are full of placeholders
+const STOP_WORDS: [&str; 29] = [
+    "a",
+    "an",
+    "and",
+    "as",
+    "at",
+    "be",
+    "by",
+    "etc",
+    "for",
+    "from",
+    "in",
+    "into",
+    "is",
+    "it",
+    "of",
+    "on",
+    "one",
+    "one's",
+    "oneself",
+    "or",
+    "somebody",
+    "someone",
+    "something",
+    "sth",
+    "that",
+    "the",
+    "this",
+    "to",
+    "with",
+];
+
+/// Lowercase words of an english text with their position.
+pub fn words(text: &str) -> impl Iterator<Item = (usize, String)> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|word| word.trim_matches('\''))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .enumerate()
+}
+
+/// Crude suffix stripping so eating, eats and eat all become eat.
+pub fn stem(word: &str) -> String {
+    let mut stem = word.to_string();
+    if stem.chars().count() <= 3 || !stem.is_ascii() {
+        return stem;
+    }
+    for (suffix, replacement) in [("ies", "i"), ("ied", "i"), ("ing", ""), ("ed", "")] {
+        if let Some(prefix) = stem.strip_suffix(suffix).filter(|prefix| prefix.len() >= 3) {
+            stem = [prefix, replacement].concat();
+            // running and stopped lose the doubled consonant
+            let bytes = stem.as_bytes();
+            let n = bytes.len();
+            if n >= 2 && bytes[n - 1] == bytes[n - 2] && !b"aeioulsz".contains(&bytes[n - 1]) {
+                stem.pop();
+            }
+            return stem;
+        }
+    }
+    if let Some(prefix) = ["sses", "ches", "shes", "xes"]
+        .iter()
+        .find_map(|suffix| stem.strip_suffix(suffix).map(|prefix| (prefix, suffix)))
+        .map(|(prefix, suffix)| [prefix, &suffix[..suffix.len() - 2]].concat())
+    {
+        return prefix;
+    }
+    if stem.ends_with('s') && !stem.ends_with("ss") && !stem.ends_with("us") {
+        stem.pop();
+    }
+    if stem.ends_with('y') {
+        stem.pop();
+        stem.push('i');
+    } else if stem.ends_with('e') {
+        stem.pop();
+    }
+    stem
+}
+
+/// Stemmed words of `text` that are not stop words, with their position.
+pub fn terms(text: &str) -> Vec<(usize, String)> {
+    words(text)
+        .filter(|(_, word)| !STOP_WORDS.contains(&word.as_str()))
+        .map(|(position, word)| (position, stem(&word)))
+        .collect()
+}
+
+/// Terms of a gloss without notes in parentheses, e.g. dog for
+/// `dog (Canis (lupus) familiaris)`.
+pub fn gloss_terms(gloss: &str) -> Vec<String> {
+    let mut depth = 0;
+    let text: String = gloss
+        .chars()
+        .filter(|&c| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => return depth == 0,
+            }
+            false
+        })
+        .collect();
+    terms(&text).into_iter().map(|(_, term)| term).collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Posting {
+    entry: u32,
+    meaning: u16,
+    position: u16,
+}
+
+/// Inverted index from stemmed english terms to the meanings of words, kanji
+/// and radicals they appear in. Names only match their exact translations.
+pub struct MeaningIndex {
+    postings: HashMap<String, Vec<Posting>>,
+}
+
+impl MeaningIndex {
+    pub fn create(entries: &[DictionaryEntry]) -> MeaningIndex {
+        let mut postings: HashMap<String, Vec<Posting>> = HashMap::new();
+        for (index, entry) in entries.iter().enumerate() {
+            if matches!(entry, DictionaryEntry::Name(_)) {
+                continue;
+            }
+            for (meaning, text) in entry.meanings().enumerate() {
+                for (position, term) in terms(text) {
+                    postings.entry(term).or_default().push(Posting {
+                        entry: index as u32,
+                        meaning: meaning as u16,
+                        position: position as u16,
+                    });
+                }
+            }
+        }
+        MeaningIndex { postings }
+    }
+
+    /// Indices of the entries with a meaning containing every term of the
+    /// query, or the exact phrase if `phrase` is set.
+    pub fn search(&self, query: &str, phrase: bool) -> Vec<usize> {
+        let terms = terms(query);
+        let Some(postings) = terms
+            .iter()
+            .map(|(_, term)| self.postings.get(term))
+            .collect::<Option<Vec<&Vec<Posting>>>>()
+        else {
+            return Vec::new();
+        };
+        let Some((first, rest)) = postings.split_first() else {
+            return Vec::new();
+        };
+        // positions of every other term by entry and meaning
+        let others: Vec<HashMap<(u32, u16), Vec<u16>>> = rest
+            .iter()
+            .map(|postings| {
+                let mut positions: HashMap<(u32, u16), Vec<u16>> = HashMap::new();
+                for posting in postings.iter() {
+                    positions
+                        .entry((posting.entry, posting.meaning))
+                        .or_default()
+                        .push(posting.position);
+                }
+                positions
+            })
+            .collect();
+        let mut entries: Vec<usize> = first
+            .iter()
+            .filter(|posting| {
+                others
+                    .iter()
+                    .zip(terms.iter().skip(1))
+                    .all(|(positions, (offset, _))| {
+                        positions
+                            .get(&(posting.entry, posting.meaning))
+                            .is_some_and(|positions| {
+                                !phrase
+                                    || positions.iter().any(|&position| {
+                                        position as usize
+                                            == posting.position as usize + offset - terms[0].0
+                                    })
+                            })
+                    })
+            })
+            .map(|posting| posting.entry as usize)
+            .collect();
+        entries.dedup();
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        for (word, expected) in [
+            ("eating", "eat"),
+            ("eats", "eat"),
+            ("running", "run"),
+            ("received", "receiv"),
+            ("receive", "receiv"),
+            ("studies", "studi"),
+            ("study", "studi"),
+            ("days", "dai"),
+            ("boxes", "box"),
+            ("glass", "glass"),
+            ("cat", "cat"),
+        ] {
+            assert_eq!(stem(word), expected, "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_terms() {
+        assert_eq!(terms("to eat something"), vec![(1, "eat".to_string())]);
+        assert_eq!(gloss_terms("dog (Canis (lupus) familiaris)"), vec!["dog"]);
+    }
+}
@@ -0,0 +1,374 @@
+use std::fmt;
+
+use crate::verb_conjugation_utils::GrammaticalForm;
+
+const SMALL_KANA: &str = "ゃゅょぁぃぅぇぉゎャュョァィゥェォヮ";
+const DOWNSTEP: char = 'ꜜ';
+// morae that cannot carry the accent, it moves to the mora before them
+const TONELESS_MORAE: [&str; 3] = ["っ", "ん", "ー"];
+// morae starting the te and ta forms, including the contracted ちゃう and とく
+const TE_MORAE: [&str; 8] = ["て", "で", "た", "だ", "ちゃ", "じゃ", "と", "ど"];
+
+const SVG_MORA_WIDTH: usize = 35;
+const SVG_HIGH: usize = 15;
+const SVG_LOW: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PitchPattern {
+    Heiban,
+    Atamadaka,
+    Nakadaka,
+    Odaka,
+}
+
+impl fmt::Display for PitchPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PitchPattern::Heiban => write!(f, "heiban"),
+            PitchPattern::Atamadaka => write!(f, "atamadaka"),
+            PitchPattern::Nakadaka => write!(f, "nakadaka"),
+            PitchPattern::Odaka => write!(f, "odaka"),
+        }
+    }
+}
+
+/// Splits a reading into morae, small kana belong to the mora before them.
+pub fn split_morae(reading: &str) -> Vec<String> {
+    let mut morae: Vec<String> = Vec::new();
+    for c in reading.chars() {
+        match morae.last_mut() {
+            Some(mora) if SMALL_KANA.contains(c) => mora.push(c),
+            _ => morae.push(c.to_string()),
+        }
+    }
+    morae
+}
+
+/// A reading with the mora after which the pitch drops, 0 if it never drops.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PitchAccent {
+    morae: Vec<String>,
+    position: usize,
+}
+
+impl PitchAccent {
+    pub fn new(reading: &str, position: usize) -> PitchAccent {
+        PitchAccent {
+            morae: split_morae(reading),
+            position,
+        }
+    }
+
+    pub fn pattern(&self) -> PitchPattern {
+        match self.position {
+            0 => PitchPattern::Heiban,
+            1 => PitchPattern::Atamadaka,
+            position if position >= self.morae.len() => PitchPattern::Odaka,
+            _ => PitchPattern::Nakadaka,
+        }
+    }
+
+    /// Whether each mora and a following particle are high.
+    pub fn levels(&self) -> Vec<bool> {
+        (0..=self.morae.len())
+            .map(|index| match self.position {
+                0 => index > 0,
+                1 => index == 0,
+                position => index > 0 && index < position,
+            })
+            .collect()
+    }
+
+    /// The reading with ꜜ after the mora where the pitch drops.
+    pub fn marked(&self) -> String {
+        let mut reading = String::new();
+        for (index, mora) in self.morae.iter().enumerate() {
+            reading.push_str(mora);
+            if index + 1 == self.position {
+                reading.push(DOWNSTEP);
+            }
+        }
+        reading
+    }
+
+    pub fn to_svg(&self) -> String {
+        let levels = self.levels();
+        let width = SVG_MORA_WIDTH * (levels.len() + 1);
+        let point = |index: usize| {
+            let y = match levels[index] {
+                true => SVG_HIGH,
+                false => SVG_LOW,
+            };
+            (SVG_MORA_WIDTH * (index + 1), y)
+        };
+        let line = (0..levels.len())
+            .map(|index| {
+                let (x, y) = point(index);
+                format!("{},{}", x, y)
+            })
+            .collect::<Vec<String>>()
+            .join(" ");
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"75\" viewBox=\"0 0 {} 75\">\n",
+            width, width
+        );
+        svg.push_str(&format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"black\" stroke-width=\"2\"/>\n",
+            line
+        ));
+        for index in 0..levels.len() {
+            let (x, y) = point(index);
+            // the particle after the word is drawn hollow
+            let fill = match index < self.morae.len() {
+                true => "black",
+                false => "white",
+            };
+            svg.push_str(&format!(
+                "<circle cx=\"{}\" cy=\"{}\" r=\"5\" fill=\"{}\" stroke=\"black\" stroke-width=\"2\"/>\n",
+                x, y, fill
+            ));
+        }
+        for (index, mora) in self.morae.iter().enumerate() {
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"68\" text-anchor=\"middle\" font-size=\"18\">{}</text>\n",
+                SVG_MORA_WIDTH * (index + 1),
+                mora
+            ));
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+/// Index of the first mora out of `candidates` at or after `from`.
+fn find_mora(morae: &[String], from: usize, candidates: &[&str]) -> Option<usize> {
+    morae
+        .iter()
+        .skip(from)
+        .position(|mora| candidates.contains(&mora.as_str()))
+        .map(|index| index + from)
+}
+
+fn followed_by(morae: &[String], index: usize, candidates: &[&str]) -> bool {
+    morae
+        .get(index + 1)
+        .is_some_and(|mora| candidates.contains(&mora.as_str()))
+}
+
+fn settle(morae: &[String], position: usize) -> usize {
+    let mut position = position.min(morae.len());
+    while position > 1 && TONELESS_MORAE.contains(&morae[position - 1].as_str()) {
+        position -= 1;
+    }
+    position
+}
+
+/// Accent of a conjugated verb from the accent of its dictionary form after
+/// the Tokyo rules, e.g. 食べる(2) gives 食べない(2), 食べた(1) and 食べます(3).
+pub fn verb_accent(
+    reading: &str,
+    accent: usize,
+    conjugated: &str,
+    forms: &[GrammaticalForm],
+) -> Option<usize> {
+    use GrammaticalForm::*;
+    let has = |form| forms.contains(&form);
+    if has(Honorific) || has(Humble) {
+        return None;
+    }
+    let dictionary = split_morae(reading);
+    let morae = split_morae(conjugated);
+    let length = morae.len();
+    // the stem is what the conjugated form keeps of the dictionary form
+    let stem = dictionary
+        .iter()
+        .zip(morae.iter())
+        .take_while(|(a, b)| a == b)
+        .count()
+        .min(dictionary.len().saturating_sub(1));
+    let flat = accent == 0;
+    // passive, causative and potential verbs are accented on their own, so
+    // the accent of the dictionary form does not limit theirs
+    let derived = has(Passive) || has(Causative) || has(Potential);
+    let limit = match derived {
+        true => length,
+        false => accent,
+    };
+    let auxiliary = has(Progressive) || has(Completive) || has(Preparatory) || has(Attempt);
+    let te_accent = || match find_mora(&morae, stem, &TE_MORAE) {
+        Some(te) => te.saturating_sub(1).clamp(1, limit),
+        None => limit,
+    };
+    let position = if auxiliary && !flat {
+        te_accent()
+    } else if has(Desire) {
+        let ta = find_mora(&morae, stem, &["た"])?;
+        match flat {
+            true if followed_by(&morae, ta, &["か", "け"]) => ta + 1,
+            true => 0,
+            false => ta,
+        }
+    } else if has(Polite) {
+        let ma = find_mora(&morae, stem, &["ま"])?;
+        match followed_by(&morae, ma, &["せ", "しょ"]) {
+            true => ma + 2,
+            false => ma + 1,
+        }
+    } else if auxiliary {
+        0
+    } else if has(Imperative) || has(Volitional) {
+        length - 1
+    } else if has(Negative) {
+        match find_mora(&morae, stem, &["な"]) {
+            Some(na) if !flat => na,
+            Some(na) if followed_by(&morae, na, &["か", "け"]) => na + 1,
+            _ if !flat => limit,
+            _ => 0,
+        }
+    } else if has(ProvisionalConditional) {
+        match flat {
+            true => length - 1,
+            false => limit.min(length.saturating_sub(2)).max(1),
+        }
+    } else if flat {
+        0
+    } else if has(Past) || has(TeForm) || has(Conditional) {
+        te_accent()
+    } else if derived {
+        length - 1
+    } else if has(MasuStem) {
+        accent.min(length - 1).max(1)
+    } else {
+        accent
+    };
+    Some(settle(&morae, position))
+}
+
+/// Accent of a conjugated i-adjective from the accent of its dictionary form,
+/// e.g. 高い(2) gives 高くて(1) and 赤い(0) gives 赤かった(2).
+pub fn adjective_accent(
+    reading: &str,
+    accent: usize,
+    conjugated: &str,
+    forms: &[GrammaticalForm],
+) -> Option<usize> {
+    use GrammaticalForm::*;
+    let has = |form| forms.contains(&form);
+    let morae = split_morae(conjugated);
+    let length = morae.len();
+    let stem = split_morae(reading).len().saturating_sub(1);
+    let ending = morae.get(stem).map(String::as_str);
+    let position = if has(Volitional) || has(Appearance) || has(Excessive) {
+        length - 1
+    } else if conjugated.strip_suffix("です") == Some(reading) {
+        match accent {
+            0 => length - 2,
+            accent => accent,
+        }
+    } else if accent > 0 {
+        (accent - 1).max(1)
+    } else {
+        match ending {
+            Some("か" | "け") => stem,
+            Some("く") if followed_by(&morae, stem, &["な", "あ"]) => stem + 1,
+            _ => 0,
+        }
+    };
+    Some(settle(&morae, position))
+}
+
+/// The reading with a line over high morae and ꜜ where the pitch drops.
+impl fmt::Display for PitchAccent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let levels = self.levels();
+        let mut overline = String::new();
+        for (index, mora) in self.morae.iter().enumerate() {
+            let width = mora.chars().count() * 2;
+            let mark = match levels[index] {
+                true => '‾',
+                false => ' ',
+            };
+            overline.extend(std::iter::repeat_n(mark, width));
+            if index + 1 == self.position {
+                overline.push(' ');
+            }
+        }
+        writeln!(f, "{}", overline.trim_end())?;
+        write!(f, "{}", self.marked())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_morae() {
+        assert_eq!(split_morae("きょうと"), vec!["きょ", "う", "と"]);
+        assert_eq!(split_morae("がっこう"), vec!["が", "っ", "こ", "う"]);
+        assert_eq!(
+            split_morae("ティーシャツ"),
+            vec!["ティ", "ー", "シャ", "ツ"]
+        );
+    }
+
+    #[test]
+    fn test_pitch_patterns() {
+        let accent = |reading, position| PitchAccent::new(reading, position);
+        assert_eq!(accent("さくら", 0).pattern(), PitchPattern::Heiban);
+        assert_eq!(accent("さくら", 0).levels(), vec![false, true, true, true]);
+        assert_eq!(accent("いのち", 1).pattern(), PitchPattern::Atamadaka);
+        assert_eq!(
+            accent("いのち", 1).levels(),
+            vec![true, false, false, false]
+        );
+        assert_eq!(accent("たべる", 2).pattern(), PitchPattern::Nakadaka);
+        assert_eq!(
+            accent("たべる", 2).levels(),
+            vec![false, true, false, false]
+        );
+        assert_eq!(accent("やま", 2).pattern(), PitchPattern::Odaka);
+        assert_eq!(accent("やま", 2).levels(), vec![false, true, false]);
+    }
+
+    #[test]
+    fn test_render_pitch_accent() {
+        assert_eq!(PitchAccent::new("たべる", 2).to_string(), "  ‾‾\nたべꜜる");
+        assert_eq!(PitchAccent::new("きょう", 1).to_string(), "‾‾‾‾\nきょꜜう");
+        assert!(PitchAccent::new("さくら", 0)
+            .to_svg()
+            .contains("points=\"35,40 70,15 105,15 140,15\""));
+    }
+
+    #[test]
+    fn test_conjugated_accents() {
+        use GrammaticalForm::*;
+        let verb =
+            |conjugated, forms: &[GrammaticalForm]| verb_accent("たべる", 2, conjugated, forms);
+        assert_eq!(verb("たべない", &[Negative]), Some(2));
+        assert_eq!(verb("たべた", &[Past]), Some(1));
+        assert_eq!(verb("たべます", &[Polite]), Some(3));
+        assert_eq!(verb("たべよう", &[Volitional]), Some(3));
+        assert_eq!(verb("たべられた", &[Passive, Past]), Some(3));
+        assert_eq!(verb_accent("かえる", 1, "かえった", &[Past]), Some(1));
+        assert_eq!(verb_accent("よむ", 1, "よんだ", &[Past]), Some(1));
+        assert_eq!(verb_accent("いく", 0, "いった", &[Past]), Some(0));
+        assert_eq!(verb_accent("いく", 0, "いきます", &[Polite]), Some(3));
+        assert_eq!(
+            verb_accent("いく", 0, "いけば", &[ProvisionalConditional]),
+            Some(2)
+        );
+        assert_eq!(
+            adjective_accent("たかい", 2, "たかくて", &[TeForm]),
+            Some(1)
+        );
+        assert_eq!(
+            adjective_accent("あかい", 0, "あかかった", &[Past]),
+            Some(2)
+        );
+        assert_eq!(
+            adjective_accent("あかい", 0, "あかいです", &[Polite]),
+            Some(3)
+        );
+    }
+}
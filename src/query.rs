@@ -1,28 +1,264 @@
 use crate::{
-    kana_utils::{katakana_to_hiragana, romaji_to_katakana},
+    composite_dictionaries::{bold, Kanji},
+    deinflection::deinflect,
+    fuzzy::FuzzyIndex,
+    kana_utils::{hiragana_to_katakana, katakana_to_hiragana, romaji_to_katakana, KANJI_CHARS},
+    meaning_index::MeaningIndex,
+    pitch_accent::PitchAccent,
+    query_language::{Filter, ParsedQuery, Scope},
+    ranking::rank,
+    verb_conjugation_utils::GrammaticalForm,
+    wildcard::{WildcardIndex, WildcardPattern},
     DictionaryEntry,
 };
+use anyhow::Result;
+use itertools::Itertools;
+use lazy_static::lazy_static;
 use regex::Regex;
-use std::collections::HashMap;
+use std::{cmp::Reverse, collections::HashMap, fmt, sync::OnceLock};
 
 pub trait Query {
     fn searchable_terms(&self) -> Vec<&str>;
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchReason {
+    Vocabulary(String),
+    Reading(String),
+    Meaning(String),
+    Tag(String),
+    Code(String),
+    Filter(String),
+    Conjugation {
+        form: String,
+        base: String,
+        grammatical_forms: Vec<GrammaticalForm>,
+        /// Accent of the conjugated form, only looked up for query results
+        pitch_accent: Option<PitchAccent>,
+    },
+}
+
+impl fmt::Display for MatchReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchReason::Vocabulary(key) => write!(f, "{} (vocabulary)", key),
+            MatchReason::Reading(key) => write!(f, "{} (reading)", key),
+            MatchReason::Meaning(key) => write!(f, "\"{}\" (meaning)", key),
+            MatchReason::Tag(key) => write!(f, "tag {}", key),
+            MatchReason::Code(key) => write!(f, "{} (index code)", key),
+            MatchReason::Filter(filters) => write!(f, "{} (filters)", filters),
+            MatchReason::Conjugation {
+                form,
+                base,
+                grammatical_forms,
+                pitch_accent,
+            } => {
+                match grammatical_forms.is_empty() {
+                    true => write!(f, "{} = dictionary form of {}", form, base)?,
+                    false => write!(
+                        f,
+                        "{} = {} of {}",
+                        form,
+                        grammatical_forms.iter().rev().join(" + "),
+                        base
+                    )?,
+                }
+                match pitch_accent {
+                    Some(accent) => write!(f, " ({})", accent.marked()),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+pub struct QueryResult<'a> {
+    pub entry: &'a DictionaryEntry,
+    pub match_reason: MatchReason,
+    /// Relevance to the query, set by `ranking::rank`
+    pub score: u32,
+}
+
+impl<'a> QueryResult<'a> {
+    /// Looks up the pitch accent of the conjugated form a deinflected query matched.
+    fn with_conjugated_pitch(mut self) -> QueryResult<'a> {
+        if let (
+            MatchReason::Conjugation {
+                form,
+                grammatical_forms,
+                pitch_accent,
+                ..
+            },
+            DictionaryEntry::Word(word),
+        ) = (&mut self.match_reason, self.entry)
+        {
+            *pitch_accent = word
+                .conjugations()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|conjugation| {
+                    &conjugation.kanji_form == form || &conjugation.kana_form == form
+                })
+                .sorted_by_key(|conjugation| conjugation.grammatical_forms != *grammatical_forms)
+                .find_map(|conjugation| {
+                    Some(PitchAccent::new(
+                        &conjugation.kana_form,
+                        conjugation.pitch_accent?,
+                    ))
+                });
+        }
+        self
+    }
+}
+
+impl<'a> fmt::Display for QueryResult<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", bold("Matched"), self.match_reason)?;
+        self.entry.fmt(f)
+    }
+}
+
+// short names for kanjidic index codes, e.g. fc:1010.6
+const CODE_ALIASES: [(&str, &[&str]); 3] = [
+    ("fc", &["four_corner"]),
+    (
+        "halpern",
+        &[
+            "halpern_kkd",
+            "halpern_njecd",
+            "halpern_kkld",
+            "halpern_kkld_2ed",
+        ],
+    ),
+    ("nelson", &["nelson_c", "nelson_n"]),
+];
+
+lazy_static! {
+    static ref CODE_QUERY: Regex = Regex::new(r"^([a-z_0-9]+):(\S+)$").unwrap();
+}
+
 pub struct Dictionary<'a> {
+    entries: &'a [DictionaryEntry],
     dict_map: HashMap<&'a str, Vec<&'a DictionaryEntry>>,
+    // kanjidic index codes like skip or heisig, by code and value
+    code_map: HashMap<&'a str, HashMap<&'a str, Vec<&'a DictionaryEntry>>>,
+    // built on the first wildcard query, most lookups never need it
+    wildcard_index: OnceLock<WildcardIndex<'a>>,
+    meaning_index: OnceLock<MeaningIndex>,
+    // only needed once a query finds nothing
+    fuzzy_index: OnceLock<FuzzyIndex>,
+    // frequencies of each kind of entry, most frequent first
+    frequencies: OnceLock<HashMap<&'a str, Vec<u32>>>,
 }
 
 impl<'a> Dictionary<'a> {
-    pub fn create(entries: &'a [DictionaryEntry]) -> Dictionary {
+    pub fn create(entries: &'a [DictionaryEntry]) -> Dictionary<'a> {
         let mut map: HashMap<&'a str, Vec<&'a DictionaryEntry>> = HashMap::new();
+        let mut code_map: HashMap<&'a str, HashMap<&'a str, Vec<&'a DictionaryEntry>>> =
+            HashMap::new();
 
         for dict in entries {
             for key in dict.searchable_terms() {
                 map.entry(key).or_default().push(dict);
             }
+            if let DictionaryEntry::Kanji(kanji) = dict {
+                for (code, value) in kanji.attributes() {
+                    code_map
+                        .entry(code)
+                        .or_default()
+                        .entry(value)
+                        .or_default()
+                        .push(dict);
+                }
+            }
+        }
+        Dictionary {
+            entries,
+            dict_map: map,
+            code_map,
+            wildcard_index: OnceLock::new(),
+            meaning_index: OnceLock::new(),
+            fuzzy_index: OnceLock::new(),
+            frequencies: OnceLock::new(),
         }
-        Dictionary { dict_map: map }
+    }
+
+    /// Kanji with an index code like `skip:1-4-3` or `heisig:1809`, `*` in the
+    /// value matches any part of the code, e.g. `skip:1-*-3`. `None` if the
+    /// query isn't an index code.
+    pub fn query_code(&self, query: &str) -> Option<Vec<QueryResult<'a>>> {
+        let captures = CODE_QUERY.captures(query)?;
+        let (name, value) = (captures.get(1)?.as_str(), captures.get(2)?.as_str());
+        let codes: Vec<&str> = CODE_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == name)
+            .map(|(_, codes)| codes.to_vec())
+            .unwrap_or(vec![name]);
+        if !codes.iter().any(|code| self.code_map.contains_key(code)) {
+            return None;
+        }
+        let pattern = Regex::new(&format!(
+            "^{}$",
+            value.split('*').map(regex::escape).join("[^-]*")
+        ))
+        .ok()?;
+        let results = codes
+            .into_iter()
+            .filter_map(|code| Some((code, self.code_map.get(code)?)))
+            .flat_map(|(code, values)| {
+                values
+                    .iter()
+                    .filter(|(value, _)| pattern.is_match(value))
+                    .flat_map(move |(value, entries)| {
+                        entries.iter().map(move |entry| (code, *value, *entry))
+                    })
+            })
+            // codes are numbers separated by dashes or dots
+            .sorted_by_key(|(code, value, entry)| {
+                let numbers: Vec<u32> = value
+                    .split(['-', '.'])
+                    .filter_map(|number| number.parse().ok())
+                    .collect();
+                (*code, numbers, Reverse(entry.frequency()))
+            })
+            .map(|(code, value, entry)| QueryResult {
+                entry,
+                match_reason: MatchReason::Code(format!("{}:{}", code, value)),
+                score: 0,
+            })
+            .collect();
+        Some(results)
+    }
+
+    /// Entries written or read like a pattern such as `食べ*`, `*的` or `?き`,
+    /// romaji patterns match kana as well. `None` if the query has no
+    /// wildcards.
+    pub fn query_wildcard(&self, query: &str, filters: &[Filter]) -> Option<Vec<QueryResult<'a>>> {
+        let patterns = WildcardPattern::parse_with_kana(query);
+        if patterns.is_empty() {
+            return None;
+        }
+        let index = self
+            .wildcard_index
+            .get_or_init(|| WildcardIndex::create(self.entries));
+        let results = patterns
+            .iter()
+            .flat_map(|pattern| index.matching(pattern))
+            .unique()
+            .flat_map(|key| {
+                self.get(key)
+                    .into_iter()
+                    .filter(move |entry| entry.headwords().contains(&key))
+                    .map(move |entry| QueryResult {
+                        entry,
+                        match_reason: entry.match_reason(key),
+                        score: 0,
+                    })
+            })
+            .unique_by(|result| std::ptr::from_ref(result.entry))
+            .filter(|result| self.matches_filters(result, filters))
+            .collect();
+        Some(rank(results))
     }
 
     pub fn get(&self, key: &str) -> Vec<&'a DictionaryEntry> {
@@ -32,6 +268,13 @@ impl<'a> Dictionary<'a> {
             .unwrap_or_default()
     }
 
+    pub fn get_kanji(&self, kanji: &str) -> Option<&'a Kanji> {
+        self.get(kanji).into_iter().find_map(|entry| match entry {
+            DictionaryEntry::Kanji(entry) if entry.kanji() == kanji => Some(entry),
+            _ => None,
+        })
+    }
+
     pub fn get_all(&self, keys: &[&str]) -> Vec<&'a DictionaryEntry> {
         keys.iter()
             .flat_map(|key| {
@@ -43,36 +286,239 @@ impl<'a> Dictionary<'a> {
             .collect()
     }
 
-    pub fn query(&self, raw_query: &str) -> Vec<&DictionaryEntry> {
-        let clean_query = clean_query(raw_query);
-        let (query, flags) = extract_flags(&clean_query);
-        self.query_with_flags(&query, flags)
+    pub fn get_deinflected(&self, key: &str) -> Vec<QueryResult<'a>> {
+        let mut results: Vec<QueryResult<'a>> = Vec::new();
+        for deinflection in deinflect(key).into_iter().skip(1) {
+            for entry in self.get(&deinflection.term) {
+                let is_base_form = match entry {
+                    DictionaryEntry::Word(word) => {
+                        (word.vocabulary == deinflection.term || word.reading == deinflection.term)
+                            && deinflection
+                                .class
+                                .is_some_and(|class| word.tags().any(|tag| class.matches_tag(tag)))
+                    }
+                    _ => false,
+                };
+                if is_base_form
+                    && !results
+                        .iter()
+                        .any(|result| std::ptr::eq(result.entry, entry))
+                {
+                    results.push(QueryResult {
+                        entry,
+                        match_reason: MatchReason::Conjugation {
+                            form: key.to_string(),
+                            base: deinflection.term.clone(),
+                            grammatical_forms: deinflection.forms.clone(),
+                            pitch_accent: None,
+                        },
+                        score: 0,
+                    });
+                }
+            }
+        }
+        results
+    }
+
+    /// Results of a query in the query language of `ParsedQuery`, e.g.
+    /// `taberu`, `reading:かん #kanji` or `meaning:cat #common -#name`.
+    pub fn query(&self, raw_query: &str) -> Result<Vec<QueryResult<'a>>> {
+        let ParsedQuery {
+            text,
+            scope,
+            filters,
+        } = ParsedQuery::parse(&clean_query(raw_query))?;
+        if scope.is_none() {
+            if let Some(mut results) = self.query_code(&text) {
+                results.retain(|result| self.matches_filters(result, &filters));
+                return Ok(results);
+            }
+            if let Some(results) = self.query_wildcard(&text, &filters) {
+                return Ok(results);
+            }
+        }
+        if text.is_empty() && !filters.is_empty() {
+            return Ok(self.filter_entries(&filters));
+        }
+        // quotes around several words ask for them as a phrase
+        let phrase = raw_query.trim().starts_with(['"', '“']) && text.contains(' ');
+        Ok(self.search(&text, scope, &filters, phrase))
     }
 
-    pub fn query_with_flags(&self, query: &str, flags: Vec<&str>) -> Vec<&DictionaryEntry> {
+    pub fn query_with_filters(&self, query: &str, filters: &[Filter]) -> Vec<QueryResult<'a>> {
+        self.search(query, None, filters, false)
+    }
+
+    /// Every entry matching all filters, for queries that are nothing else.
+    fn filter_entries(&self, filters: &[Filter]) -> Vec<QueryResult<'a>> {
+        let match_reason = MatchReason::Filter(filters.iter().join(" "));
+        let results = self
+            .entries
+            .iter()
+            .filter(|entry| filters.iter().all(|filter| filter.matches(entry, self)))
+            .map(|entry| QueryResult {
+                entry,
+                match_reason: match_reason.clone(),
+                score: 0,
+            })
+            .collect();
+        rank(results)
+    }
+
+    fn search(
+        &self,
+        query: &str,
+        scope: Option<Scope>,
+        filters: &[Filter],
+        phrase: bool,
+    ) -> Vec<QueryResult<'a>> {
         let query_converted_to_katakana = romaji_to_katakana(query).ok();
         let query_converted_to_hiragana = query_converted_to_katakana
             .clone()
-            .map(|katakana| katakana_to_hiragana(&katakana).expect("Should be valid katakana"));
-        match (query_converted_to_hiragana, query_converted_to_katakana) {
-            (Some(hiragana), Some(katakana))
+            .map(|katakana| katakana_to_hiragana(&katakana));
+        let keys: Vec<String> = match (
+            scope,
+            query_converted_to_hiragana,
+            query_converted_to_katakana,
+        ) {
+            (Some(Scope::Meaning), _, _) => vec![query.to_string()],
+            // kanji readings are katakana for on'yomi and hiragana for kun'yomi
+            (Some(Scope::Reading), hiragana, _) => {
+                let hiragana = match query.is_ascii() {
+                    true => hiragana.unwrap_or(query.to_string()),
+                    false => katakana_to_hiragana(query),
+                };
+                let katakana = hiragana_to_katakana(&hiragana);
+                vec![hiragana, katakana]
+            }
+            (None, Some(hiragana), Some(katakana))
                 if self.worth_converting(query, &[&hiragana, &katakana]) =>
             {
-                self.get_all(&[&hiragana, &katakana])
+                vec![hiragana, katakana]
+            }
+            _ => vec![query.to_string()],
+        };
+        let exact_results = keys.iter().flat_map(|key| {
+            self.get(key).into_iter().map(|entry| QueryResult {
+                entry,
+                match_reason: entry.match_reason(key),
+                score: 0,
+            })
+        });
+        let deinflected_results = keys.iter().flat_map(|key| self.get_deinflected(key));
+        let mut results: Vec<QueryResult<'a>> = exact_results.chain(deinflected_results).collect();
+        if keys == [query] && scope != Some(Scope::Reading) {
+            for entry in self.search_meanings(query, phrase) {
+                if !results
+                    .iter()
+                    .any(|result| std::ptr::eq(result.entry, entry))
+                {
+                    results.push(QueryResult {
+                        entry,
+                        match_reason: MatchReason::Meaning(query.to_string()),
+                        score: 0,
+                    });
+                }
             }
-            _ => self.get(query),
         }
-        .into_iter()
-        .filter(|item| {
-            flags.is_empty()
-                || flags.contains(&format!("#{}", &item.name().to_lowercase()).as_str())
-        })
-        .collect()
+        results.retain(|result| {
+            let in_scope = match (scope, &result.match_reason) {
+                (None, _) => true,
+                (Some(Scope::Meaning), match_reason) => {
+                    matches!(match_reason, MatchReason::Meaning(_))
+                }
+                // a headword without kanji is read as it is written
+                (Some(Scope::Reading), MatchReason::Vocabulary(key)) => !KANJI_CHARS.is_match(key),
+                (Some(Scope::Reading), match_reason) => matches!(
+                    match_reason,
+                    MatchReason::Reading(_) | MatchReason::Conjugation { .. }
+                ),
+            };
+            in_scope && self.matches_filters(result, filters)
+        });
+        rank(
+            results
+                .into_iter()
+                .map(QueryResult::with_conjugated_pitch)
+                .collect(),
+        )
+    }
+
+    /// Entries with a meaning containing every word of an english query in
+    /// any form, e.g. 食べる for "eating" or "to eat something".
+    pub fn search_meanings(&self, query: &str, phrase: bool) -> Vec<&'a DictionaryEntry> {
+        if !query.chars().any(|c| c.is_ascii_alphabetic()) {
+            return Vec::new();
+        }
+        let index = self
+            .meaning_index
+            .get_or_init(|| MeaningIndex::create(self.entries));
+        index
+            .search(query, phrase)
+            .into_iter()
+            .map(|index| &self.entries[index])
+            .collect()
+    }
+
+    /// Spellings close to a query that found nothing, e.g. taberu for tabreru
+    /// or receive for recieve. Queries whose filters rule out everything they
+    /// find get none.
+    pub fn suggest(&self, raw_query: &str) -> Vec<String> {
+        let Ok(query) = ParsedQuery::parse(&clean_query(raw_query)) else {
+            return Vec::new();
+        };
+        let filtered_out = !query.filters.is_empty()
+            && !self.search(&query.text, query.scope, &[], false).is_empty();
+        if query.text.is_empty() || filtered_out {
+            return Vec::new();
+        }
+        self.fuzzy_index
+            .get_or_init(|| FuzzyIndex::create(self.entries))
+            .suggest(&query.text)
+            .into_iter()
+            .map(|text| {
+                ParsedQuery {
+                    text,
+                    scope: query.scope,
+                    filters: query.filters.clone(),
+                }
+                .to_string()
+            })
+            .collect()
+    }
+
+    /// Position of an entry among all entries of its kind ordered by their
+    /// frequency, 1 for the most frequent.
+    pub fn frequency_rank(&self, entry: &DictionaryEntry) -> Option<u32> {
+        let frequency = entry.frequency()?;
+        let frequencies = self.frequencies.get_or_init(|| {
+            let mut frequencies: HashMap<&'a str, Vec<u32>> = self
+                .entries
+                .iter()
+                .filter_map(|entry| Some((entry.name(), entry.frequency()?)))
+                .into_group_map();
+            for kind in frequencies.values_mut() {
+                kind.sort_unstable_by_key(|&frequency| Reverse(frequency));
+            }
+            frequencies
+        });
+        let ranked = frequencies.get(entry.name())?;
+        Some(ranked.partition_point(|&other| other > frequency) as u32 + 1)
+    }
+
+    fn matches_filters(&self, result: &QueryResult, filters: &[Filter]) -> bool {
+        filters
+            .iter()
+            .all(|filter| filter.matches(result.entry, self))
     }
 
     fn worth_converting(&self, query: &str, converted: &[&str]) -> bool {
         let num_result_normal = self.get(query).len();
-        let num_result_converted = self.get_all(converted).len();
+        let num_result_converted = self.get_all(converted).len()
+            + converted
+                .iter()
+                .map(|key| self.get_deinflected(key).len())
+                .sum::<usize>();
         num_result_converted > num_result_normal
     }
 }
@@ -83,9 +529,57 @@ fn clean_query(query: &str) -> String {
         .to_lowercase()
 }
 
-fn extract_flags(query: &str) -> (String, Vec<&str>) {
-    let pattern = Regex::new(r"#(kanji|word|name|radical)").unwrap();
-    let flags: Vec<&str> = pattern.find_iter(query).map(|f| f.as_str()).collect();
-    let cleaned_string: String = pattern.replace_all(query, "").trim().to_string();
-    (cleaned_string, flags)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::kanji_entry;
+
+    #[test]
+    fn test_query_code() {
+        let entries = vec![
+            kanji_entry(
+                "日",
+                &[
+                    ("skip", "3-3-1"),
+                    ("four_corner", "6010.0"),
+                    ("halpern_njecd", "3027"),
+                    ("nelson_c", "2097"),
+                    ("nelson_n", "2449"),
+                ],
+            ),
+            kanji_entry(
+                "本",
+                &[("skip", "4-5-1"), ("halpern_kkd", "3"), ("nelson_c", "96")],
+            ),
+        ];
+        let dict = Dictionary::create(&entries);
+        let codes = |query: &str| -> Option<Vec<String>> {
+            let results = dict.query_code(query)?;
+            Some(
+                results
+                    .iter()
+                    .map(|result| match (result.entry, &result.match_reason) {
+                        (DictionaryEntry::Kanji(kanji), MatchReason::Code(code)) => {
+                            format!("{} {}", kanji.kanji(), code)
+                        }
+                        _ => panic!("code queries only find kanji"),
+                    })
+                    .collect(),
+            )
+        };
+        assert_eq!(codes("skip:3-3-1").unwrap(), ["日 skip:3-3-1"]);
+        assert_eq!(
+            codes("skip:*-*-1").unwrap(),
+            ["日 skip:3-3-1", "本 skip:4-5-1"]
+        );
+        assert_eq!(codes("skip:4-*").unwrap(), Vec::<String>::new());
+        assert_eq!(codes("fc:6010.0").unwrap(), ["日 four_corner:6010.0"]);
+        assert_eq!(codes("halpern:3027").unwrap(), ["日 halpern_njecd:3027"]);
+        assert_eq!(
+            codes("nelson:*").unwrap(),
+            ["本 nelson_c:96", "日 nelson_c:2097", "日 nelson_n:2449"]
+        );
+        assert_eq!(codes("heisig:1"), None);
+        assert_eq!(codes("日本"), None);
+    }
 }
@@ -0,0 +1,410 @@
+use std::{error::Error, fmt, ops::RangeInclusive};
+
+use itertools::Itertools;
+
+use crate::{kana_utils::KANJI_CHARS, ranking::is_priority_tag, Dictionary, DictionaryEntry};
+
+const KINDS: [&str; 4] = ["Kanji", "Word", "Name", "Radical"];
+// every filter with an example of its use
+const FILTERS: [(&str, &str); 11] = [
+    ("kanji", "#kanji"),
+    ("word", "#word"),
+    ("name", "#name"),
+    ("radical", "#radical"),
+    ("common", "#common"),
+    ("jlpt", "#jlpt:n3"),
+    ("grade", "#grade:<=6"),
+    ("strokes", "#strokes:10-12"),
+    ("freq", "#freq:<5000"),
+    ("pos", "#pos:v5r"),
+    ("tag", "#tag:uk"),
+];
+const OR: [&str; 2] = ["or", "|"];
+// words this far up the frequency ranking count as common
+const COMMON_RANK: u32 = 10000;
+// jouyou kanji are taught up to grade 8, jinmeiyou kanji have grade 9 or 10
+const COMMON_GRADE: u8 = 8;
+
+/// A condition on the entries a query finds, e.g. `#jlpt:n3` or `-#name`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    Kind(&'static str),
+    Jlpt(RangeInclusive<u32>),
+    Grade(RangeInclusive<u32>),
+    Strokes(RangeInclusive<u32>),
+    /// Rank among the entries of the same kind, 1 is the most frequent
+    Frequency(RangeInclusive<u32>),
+    PartOfSpeech(String),
+    Tag(String),
+    Common,
+    Not(Box<Filter>),
+    Any(Vec<Filter>),
+}
+
+/// The part of an entry a query is matched against, e.g. `reading:かん`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scope {
+    Reading,
+    Meaning,
+}
+
+/// A query split into the text to search for and the filters on its
+/// results, e.g. `meaning:cat #common -#name`. Filters joined by `|` or `or`
+/// need only one of them to match, all others have to match.
+#[derive(Debug, PartialEq)]
+pub struct ParsedQuery {
+    pub text: String,
+    pub scope: Option<Scope>,
+    pub filters: Vec<Filter>,
+}
+
+impl ParsedQuery {
+    pub fn parse(query: &str) -> Result<ParsedQuery, QueryParseError> {
+        let tokens: Vec<&str> = query.split_whitespace().collect();
+        let is_filter = |token: &&str| token.starts_with('#') || token.starts_with("-#");
+        let mut text: Vec<&str> = Vec::new();
+        let mut scope: Option<(Scope, &str)> = None;
+        let mut filters: Vec<Filter> = Vec::new();
+        let mut or = false;
+        for (index, token) in tokens.iter().enumerate() {
+            if is_filter(token) {
+                let filter = parse_alternatives(token)?;
+                let filter = match filters.pop() {
+                    Some(previous) if or => previous.or(filter),
+                    previous => {
+                        filters.extend(previous);
+                        filter
+                    }
+                };
+                filters.push(filter);
+                or = false;
+                continue;
+            }
+            if OR.contains(token) {
+                let before = index > 0 && is_filter(&tokens[index - 1]);
+                let after = tokens.get(index + 1).is_some_and(is_filter);
+                match (before, after) {
+                    (true, true) => {
+                        or = true;
+                        continue;
+                    }
+                    (false, false) => {}
+                    _ => return Err(QueryParseError::new(token, "filters on both sides")),
+                }
+            }
+            let token = match token.split_once(':') {
+                Some((name @ ("reading" | "meaning"), rest)) => {
+                    let new = match name {
+                        "reading" => Scope::Reading,
+                        _ => Scope::Meaning,
+                    };
+                    if scope.is_some_and(|(scope, _)| scope != new) {
+                        return Err(QueryParseError::new(
+                            token,
+                            "only one of reading: or meaning:",
+                        ));
+                    }
+                    scope = Some((new, token));
+                    rest
+                }
+                _ => token,
+            };
+            if !token.is_empty() {
+                text.push(token);
+            }
+        }
+        if let (Some((_, token)), true) = (scope, text.is_empty()) {
+            return Err(QueryParseError::new(token, "something to search for"));
+        }
+        Ok(ParsedQuery {
+            text: text.join(" "),
+            scope: scope.map(|(scope, _)| scope),
+            filters,
+        })
+    }
+}
+
+impl fmt::Display for ParsedQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.scope {
+            Some(Scope::Reading) => write!(f, "reading:{}", self.text)?,
+            Some(Scope::Meaning) => write!(f, "meaning:{}", self.text)?,
+            None => write!(f, "{}", self.text)?,
+        }
+        for filter in self.filters.iter() {
+            write!(f, " {}", filter)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_alternatives(token: &str) -> Result<Filter, QueryParseError> {
+    let mut alternatives: Vec<Filter> = token
+        .split('|')
+        .map(|alternative| match alternative {
+            "" => Err(QueryParseError::new(token, "filters on both sides of |")),
+            alternative => parse_filter(alternative),
+        })
+        .collect::<Result<_, _>>()?;
+    match alternatives.len() {
+        1 => Ok(alternatives.remove(0)),
+        _ => Ok(Filter::Any(alternatives)),
+    }
+}
+
+fn parse_filter(token: &str) -> Result<Filter, QueryParseError> {
+    if let Some(filter) = token.strip_prefix('-') {
+        return Ok(Filter::Not(Box::new(parse_filter(filter)?)));
+    }
+    let unknown = || {
+        let filters = FILTERS.iter().map(|(_, example)| example).join(", ");
+        QueryParseError::new(token, &format!("one of {}", filters))
+    };
+    let filter = token.strip_prefix('#').ok_or_else(unknown)?;
+    let (name, value) = match filter.split_once(':') {
+        Some((name, value)) => (name, Some(value)),
+        None => (filter, None),
+    };
+    let (_, example) = FILTERS
+        .iter()
+        .find(|(filter, _)| *filter == name)
+        .ok_or_else(unknown)?;
+    let invalid = || QueryParseError::new(token, &format!("a filter like {}", example));
+    let range = |value: &str| parse_range(value).ok_or_else(invalid);
+    match (name, value) {
+        ("common", None) => Ok(Filter::Common),
+        (kind, None) => KINDS
+            .into_iter()
+            .find(|name| name.to_lowercase() == kind)
+            .map(Filter::Kind)
+            .ok_or_else(invalid),
+        // n3 and 3 are the same level
+        ("jlpt", Some(value)) => Some(range(&value.replace('n', ""))?)
+            .filter(|levels| *levels.start() <= 5 && *levels.end() >= 1)
+            .map(Filter::Jlpt)
+            .ok_or_else(invalid),
+        ("grade", Some(value)) => Ok(Filter::Grade(range(value)?)),
+        ("strokes", Some(value)) => Ok(Filter::Strokes(range(value)?)),
+        ("freq", Some(value)) => Ok(Filter::Frequency(range(value)?)),
+        ("pos", Some(value)) if !value.is_empty() => Ok(Filter::PartOfSpeech(value.to_string())),
+        ("tag", Some(value)) if !value.is_empty() => Ok(Filter::Tag(value.to_string())),
+        _ => Err(invalid()),
+    }
+}
+
+/// A number like `6`, a comparison like `<=6` or a range like `3-5`.
+fn parse_range(value: &str) -> Option<RangeInclusive<u32>> {
+    if let Some(end) = value.strip_prefix("<=") {
+        return Some(0..=end.parse().ok()?);
+    }
+    if let Some(start) = value.strip_prefix(">=") {
+        return Some(start.parse().ok()?..=u32::MAX);
+    }
+    if let Some(end) = value.strip_prefix('<') {
+        return Some(0..=end.parse::<u32>().ok()?.checked_sub(1)?);
+    }
+    if let Some(start) = value.strip_prefix('>') {
+        return Some(start.parse::<u32>().ok()?.checked_add(1)?..=u32::MAX);
+    }
+    if let Some((start, end)) = value.split_once('-') {
+        return Some(start.parse().ok()?..=end.parse().ok()?);
+    }
+    let value = value.strip_prefix('=').unwrap_or(value).parse().ok()?;
+    Some(value..=value)
+}
+
+fn format_range(range: &RangeInclusive<u32>) -> String {
+    match (*range.start(), *range.end()) {
+        (start, end) if start == end => start.to_string(),
+        (0, end) => format!("<={}", end),
+        (start, u32::MAX) => format!(">={}", start),
+        (start, end) => format!("{}-{}", start, end),
+    }
+}
+
+impl Filter {
+    fn or(self, other: Filter) -> Filter {
+        match self {
+            Filter::Any(mut alternatives) => {
+                alternatives.push(other);
+                Filter::Any(alternatives)
+            }
+            filter => Filter::Any(vec![filter, other]),
+        }
+    }
+
+    pub fn matches(&self, entry: &DictionaryEntry, dict: &Dictionary) -> bool {
+        let in_range = |range: &RangeInclusive<u32>, value: Option<u32>| {
+            value.is_some_and(|value| range.contains(&value))
+        };
+        match self {
+            Filter::Kind(kind) => entry.name() == *kind,
+            Filter::Jlpt(levels) => jlpt(entry, dict).is_some_and(|entry_levels| {
+                levels.start() <= entry_levels.end() && entry_levels.start() <= levels.end()
+            }),
+            Filter::Grade(grades) => match entry {
+                DictionaryEntry::Kanji(kanji) => in_range(grades, kanji.grade().map(u32::from)),
+                _ => false,
+            },
+            Filter::Strokes(strokes) => match entry {
+                DictionaryEntry::Kanji(kanji) => in_range(strokes, Some(kanji.strokes().into())),
+                DictionaryEntry::Radical(radical) => {
+                    in_range(strokes, Some(radical.strokes().into()))
+                }
+                _ => false,
+            },
+            Filter::Frequency(ranks) => in_range(ranks, dict.frequency_rank(entry)),
+            Filter::PartOfSpeech(pos) => entry
+                .tags()
+                .any(|(name, tag)| tag.is_part_of_speech() && name.eq_ignore_ascii_case(pos)),
+            Filter::Tag(tag) => entry.tags().any(|(name, _)| name.eq_ignore_ascii_case(tag)),
+            Filter::Common => match entry {
+                DictionaryEntry::Word(word) => {
+                    word.tags().any(is_priority_tag)
+                        || in_range(&(1..=COMMON_RANK), dict.frequency_rank(entry))
+                }
+                DictionaryEntry::Kanji(kanji) => kanji.grade().is_some_and(|g| g <= COMMON_GRADE),
+                _ => false,
+            },
+            Filter::Not(filter) => !filter.matches(entry, dict),
+            Filter::Any(filters) => filters.iter().any(|filter| filter.matches(entry, dict)),
+        }
+    }
+}
+
+/// Levels of a kanji, or of the hardest kanji a word is written with.
+fn jlpt(entry: &DictionaryEntry, dict: &Dictionary) -> Option<RangeInclusive<u32>> {
+    let levels = match entry {
+        DictionaryEntry::Kanji(kanji) => kanji.jlpt(),
+        // lower levels are harder, and words without kanji have no level
+        DictionaryEntry::Word(word) => KANJI_CHARS
+            .find_iter(&word.vocabulary)
+            .map(|kanji| dict.get_kanji(kanji.as_str())?.jlpt())
+            .collect::<Option<Vec<RangeInclusive<u8>>>>()?
+            .into_iter()
+            .min_by_key(|levels| *levels.start()),
+        _ => None,
+    }?;
+    Some(u32::from(*levels.start())..=u32::from(*levels.end()))
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Filter::Kind(kind) => write!(f, "#{}", kind.to_lowercase()),
+            Filter::Jlpt(levels) if levels.start() == levels.end() => {
+                write!(f, "#jlpt:n{}", levels.start())
+            }
+            Filter::Jlpt(levels) => write!(f, "#jlpt:{}", format_range(levels)),
+            Filter::Grade(grades) => write!(f, "#grade:{}", format_range(grades)),
+            Filter::Strokes(strokes) => write!(f, "#strokes:{}", format_range(strokes)),
+            Filter::Frequency(ranks) => write!(f, "#freq:{}", format_range(ranks)),
+            Filter::PartOfSpeech(pos) => write!(f, "#pos:{}", pos),
+            Filter::Tag(tag) => write!(f, "#tag:{}", tag),
+            Filter::Common => write!(f, "#common"),
+            Filter::Not(filter) => write!(f, "-{}", filter),
+            Filter::Any(filters) => write!(f, "{}", filters.iter().join("|")),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct QueryParseError {
+    token: String,
+    expected: String,
+}
+
+impl QueryParseError {
+    fn new(token: &str, expected: &str) -> QueryParseError {
+        QueryParseError {
+            token: token.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+}
+
+impl Error for QueryParseError {}
+
+impl fmt::Display for QueryParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Cannot understand '{}', expected {}",
+            self.token, self.expected
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{kanji_entry, word_entry};
+
+    #[test]
+    fn test_parse_query() {
+        let parsed = ParsedQuery::parse("meaning:black cat #jlpt:n3|#grade:<=6 -#name").unwrap();
+        assert_eq!(parsed.text, "black cat");
+        assert_eq!(parsed.scope, Some(Scope::Meaning));
+        assert_eq!(
+            parsed.filters,
+            vec![
+                Filter::Any(vec![Filter::Jlpt(3..=3), Filter::Grade(0..=6)]),
+                Filter::Not(Box::new(Filter::Kind("Name"))),
+            ]
+        );
+        let parsed = ParsedQuery::parse("#strokes:10-12 or #freq:<5000 #pos:v5r").unwrap();
+        assert_eq!(parsed.text, "");
+        assert_eq!(
+            parsed.filters.iter().join(" "),
+            "#strokes:10-12|#freq:<=4999 #pos:v5r"
+        );
+        assert_eq!(
+            ParsedQuery::parse("black or white").unwrap().text,
+            "black or white"
+        );
+        assert_eq!(
+            ParsedQuery::parse("#kanji").unwrap().filters,
+            [Filter::Kind("Kanji")]
+        );
+        for malformed in [
+            "#jlpt:n7",
+            "#grade:six",
+            "#foo",
+            "#word:1",
+            "#pos:",
+            "#kanji|",
+            "#kanji or",
+            "reading:",
+            "reading:かん meaning:cat",
+        ] {
+            assert!(ParsedQuery::parse(malformed).is_err(), "{}", malformed);
+        }
+        assert_eq!(
+            ParsedQuery::parse("犬 #grade:x").unwrap_err().to_string(),
+            "Cannot understand '#grade:x', expected a filter like #grade:<=6"
+        );
+    }
+
+    #[test]
+    fn test_jlpt_filter() {
+        let entries = vec![
+            kanji_entry("日", &[("jlpt", "4")]),
+            kanji_entry("会", &[("jlpt", "3")]),
+            kanji_entry("議", &[("jlpt", "2")]),
+            word_entry("会議", "かいぎ", &["n"], None),
+        ];
+        let dict = Dictionary::create(&entries);
+        let matching = |query: &str| -> Vec<usize> {
+            let filter = &ParsedQuery::parse(query).unwrap().filters[0];
+            (0..entries.len())
+                .filter(|&index| filter.matches(&entries[index], &dict))
+                .collect()
+        };
+        assert_eq!(matching("#jlpt:n5"), [0]);
+        assert_eq!(matching("#jlpt:n4"), [1]);
+        assert_eq!(matching("#jlpt:n3"), [2, 3]);
+        assert_eq!(matching("#jlpt:n2"), [2, 3]);
+        assert_eq!(matching("#jlpt:n1"), Vec::<usize>::new());
+        assert_eq!(matching("#jlpt:4-5"), [0, 1]);
+    }
+}
@@ -0,0 +1,180 @@
+use anyhow::Result;
+use itertools::Itertools;
+use std::{cmp::Reverse, error::Error, fmt, ops::RangeInclusive};
+
+use crate::{
+    composite_dictionaries::{bold, Kanji, Radical},
+    Dictionary, DictionaryEntry,
+};
+
+/// Radicals a kanji has to contain, e.g. `口 木 +strokes:7` or `口 +strokes:5-8`
+#[derive(Debug, PartialEq)]
+pub struct RadicalQuery {
+    radicals: Vec<String>,
+    strokes: Option<RangeInclusive<u8>>,
+}
+
+impl RadicalQuery {
+    pub fn parse(args: &[String]) -> Result<RadicalQuery> {
+        let mut radicals: Vec<String> = Vec::new();
+        let mut strokes: Option<RangeInclusive<u8>> = None;
+        for arg in args.iter().flat_map(|arg| arg.split_whitespace()) {
+            match arg.strip_prefix("+strokes:") {
+                Some(range) => {
+                    strokes = Some(parse_range(range).ok_or(RadicalQueryError::new(arg))?)
+                }
+                None if arg.starts_with('+') => return Err(RadicalQueryError::new(arg).into()),
+                None => radicals.push(arg.to_string()),
+            }
+        }
+        if radicals.is_empty() && strokes.is_none() {
+            return Err(RadicalQueryError::new("").into());
+        }
+        Ok(RadicalQuery { radicals, strokes })
+    }
+}
+
+fn parse_range(range: &str) -> Option<RangeInclusive<u8>> {
+    match range.split_once('-') {
+        Some((start, end)) => Some(start.parse().ok()?..=end.parse().ok()?),
+        None => range.parse().ok().map(|strokes| strokes..=strokes),
+    }
+}
+
+pub struct RadicalSearch<'a> {
+    pub kanji: Vec<&'a Kanji>,
+    /// Radicals that would narrow the results down further and how many
+    /// results would remain
+    pub narrowing: Vec<(&'a Radical, usize)>,
+}
+
+fn find_radical<'a>(dict: &Dictionary<'a>, radical: &str) -> Option<&'a Radical> {
+    dict.get(radical).into_iter().find_map(|entry| match entry {
+        DictionaryEntry::Radical(entry) if entry.radical() == radical => Some(entry),
+        _ => None,
+    })
+}
+
+impl<'a> RadicalSearch<'a> {
+    /// Kanji containing every radical of the query, most frequent first.
+    pub fn search(dict: &Dictionary<'a>, query: &RadicalQuery) -> Result<RadicalSearch<'a>> {
+        let radicals: Vec<&Radical> = query
+            .radicals
+            .iter()
+            .map(|radical| find_radical(dict, radical).ok_or(RadicalQueryError::new(radical)))
+            .collect::<Result<_, _>>()?;
+        // without radicals every kanji of the stroke count is a candidate
+        let candidates: Vec<String> = match radicals.first() {
+            Some(radical) => radical.kanji().map(String::from).collect(),
+            None => (0x4E00..=0x9FFF)
+                .filter_map(char::from_u32)
+                .map(String::from)
+                .collect(),
+        };
+        let kanji: Vec<&Kanji> = candidates
+            .iter()
+            .filter(|kanji| radicals.iter().all(|radical| radical.is_part_of(kanji)))
+            .filter_map(|kanji| dict.get_kanji(kanji))
+            .filter(|kanji| {
+                query
+                    .strokes
+                    .as_ref()
+                    .is_none_or(|strokes| strokes.contains(&kanji.strokes()))
+            })
+            .sorted_by_key(|kanji| (Reverse(kanji.frequency()), kanji.strokes()))
+            .collect();
+        let narrowing: Vec<(&Radical, usize)> = kanji
+            .iter()
+            .flat_map(|kanji| kanji.radicals())
+            .unique()
+            .filter(|radical| !query.radicals.iter().any(|selected| selected == radical))
+            .filter_map(|radical| find_radical(dict, radical))
+            .map(|radical| {
+                let remaining = kanji
+                    .iter()
+                    .filter(|kanji| radical.is_part_of(kanji.kanji()))
+                    .count();
+                (radical, remaining)
+            })
+            .filter(|(_, remaining)| *remaining > 0 && *remaining < kanji.len())
+            .sorted_by_key(|(radical, _)| (radical.strokes(), radical.radical().to_string()))
+            .collect();
+        Ok(RadicalSearch { kanji, narrowing })
+    }
+}
+
+impl<'a> fmt::Display for RadicalSearch<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} {}",
+            bold(&format!("Kanji ({}):", self.kanji.len())),
+            self.kanji.iter().map(|kanji| kanji.kanji()).join("、")
+        )?;
+        if !self.narrowing.is_empty() {
+            writeln!(
+                f,
+                "{} {}",
+                bold("Narrow down:"),
+                self.narrowing
+                    .iter()
+                    .map(|(radical, remaining)| format!("{} ({})", radical.radical(), remaining))
+                    .join("、")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct RadicalQueryError {
+    argument: String,
+}
+
+impl RadicalQueryError {
+    fn new(argument: &str) -> RadicalQueryError {
+        RadicalQueryError {
+            argument: argument.to_string(),
+        }
+    }
+}
+
+impl Error for RadicalQueryError {}
+
+impl fmt::Display for RadicalQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.argument.as_str() {
+            "" => write!(f, "Expected radicals or +strokes:<count>"),
+            argument if argument.starts_with('+') => write!(
+                f,
+                "Cannot understand '{}', expected +strokes:<count> or +strokes:<min>-<max>",
+                argument
+            ),
+            argument => write!(f, "'{}' is not a known radical", argument),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<RadicalQuery> {
+        RadicalQuery::parse(&[args.to_string()])
+    }
+
+    #[test]
+    fn test_parse_radical_query() {
+        assert_eq!(
+            parse("口 木 +strokes:7").unwrap(),
+            RadicalQuery {
+                radicals: vec!["口".to_string(), "木".to_string()],
+                strokes: Some(7..=7),
+            }
+        );
+        assert_eq!(parse("+strokes:5-8").unwrap().strokes, Some(5..=8));
+        assert!(parse("口 +strokes:seven").is_err());
+        assert!(parse("口 +grade:3").is_err());
+        assert!(parse("").is_err());
+    }
+}
@@ -0,0 +1,146 @@
+use std::cmp::Reverse;
+
+use crate::{
+    kana_utils::KANJI_CHARS,
+    meaning_index::{gloss_terms, terms},
+    query::{MatchReason, QueryResult},
+    DictionaryEntry,
+};
+
+// JMdict priority markers, numbered ones like news1 or ichi2 count as well
+const PRIORITY_TAGS: [&str; 5] = ["news", "ichi", "spec", "gai", "P"];
+const PRIORITY_SCORE: u32 = 40;
+const FREQUENCY_SCORE: u32 = 10;
+const MAX_FREQUENCY_SCORE: u32 = 160;
+const FIRST_MEANING_SCORE: u32 = 150;
+const MEANING_POSITION_PENALTY: u32 = 30;
+const FIRST_GLOSS_SCORE: u32 = 50;
+const WHOLE_GLOSS_SCORE: u32 = 100;
+
+fn match_score(match_reason: &MatchReason) -> u32 {
+    match match_reason {
+        // a headword without kanji is just its reading, like the name イヌ for inu
+        MatchReason::Vocabulary(key) if !KANJI_CHARS.is_match(key) => 3000,
+        MatchReason::Vocabulary(_) | MatchReason::Code(_) => 4000,
+        MatchReason::Reading(_) => 3000,
+        MatchReason::Conjugation { .. } => 2000,
+        MatchReason::Meaning(_) => 1000,
+        MatchReason::Tag(_) | MatchReason::Filter(_) => 0,
+    }
+}
+
+fn kind_score(entry: &DictionaryEntry) -> u32 {
+    match entry {
+        DictionaryEntry::Word(_) => 200,
+        DictionaryEntry::Kanji(_) => 100,
+        DictionaryEntry::Radical(_) => 50,
+        DictionaryEntry::Name(_) => 0,
+    }
+}
+
+pub fn is_priority_tag(tag: &str) -> bool {
+    PRIORITY_TAGS.iter().any(|priority| {
+        tag.strip_prefix(priority)
+            .is_some_and(|rank| rank.chars().all(|c| c.is_ascii_digit()))
+    })
+}
+
+/// Earlier meanings score higher, as do matches on the first gloss of a
+/// meaning and glosses that are exactly the key.
+fn meaning_score(entry: &DictionaryEntry, key: &str) -> u32 {
+    let key_terms: Vec<String> = terms(key).into_iter().map(|(_, term)| term).collect();
+    let contains_key = |gloss: &str| {
+        gloss == key
+            || !key_terms.is_empty()
+                && terms(gloss)
+                    .iter()
+                    .filter(|(_, term)| key_terms.contains(term))
+                    .count()
+                    >= key_terms.len()
+    };
+    let Some((meaning, (index, gloss))) =
+        entry.meanings().enumerate().find_map(|(meaning, text)| {
+            let gloss = text
+                .split(", ")
+                .enumerate()
+                .find(|(_, gloss)| contains_key(gloss))?;
+            Some((meaning, gloss))
+        })
+    else {
+        return 0;
+    };
+    let first_gloss = match index {
+        0 => FIRST_GLOSS_SCORE,
+        _ => 0,
+    };
+    let whole_gloss = match gloss == key || !key_terms.is_empty() && gloss_terms(gloss) == key_terms
+    {
+        true => WHOLE_GLOSS_SCORE,
+        false => 0,
+    };
+    FIRST_MEANING_SCORE.saturating_sub(MEANING_POSITION_PENALTY * meaning as u32)
+        + first_gloss
+        + whole_gloss
+}
+
+/// How well a result answers its query, higher is better. The kind of match
+/// always outweighs everything else.
+pub fn score(result: &QueryResult) -> u32 {
+    let frequency_score = result.entry.frequency().map_or(0, |frequency| {
+        (frequency.saturating_add(1).ilog2() * FREQUENCY_SCORE).min(MAX_FREQUENCY_SCORE)
+    });
+    let priority_score = match result.entry {
+        DictionaryEntry::Word(word) => {
+            word.tags().filter(|tag| is_priority_tag(tag)).count() as u32 * PRIORITY_SCORE
+        }
+        _ => 0,
+    };
+    let meaning_score = match &result.match_reason {
+        MatchReason::Meaning(key) => meaning_score(result.entry, key),
+        _ => 0,
+    };
+    match_score(&result.match_reason)
+        + kind_score(result.entry)
+        + frequency_score
+        + priority_score
+        + meaning_score
+}
+
+/// Scores every result and sorts the best first, ties keep their order.
+pub fn rank(mut results: Vec<QueryResult>) -> Vec<QueryResult> {
+    for result in results.iter_mut() {
+        result.score = score(result);
+    }
+    results.sort_by_key(|result| Reverse(result.score));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::meanings_entry;
+
+    #[test]
+    fn test_rank() {
+        let dog = meanings_entry("犬", &["dog"], Some(20000));
+        let spy = meanings_entry("間者", &["spy, dog"], Some(50));
+        let rare_dog = meanings_entry("狗", &["dog"], Some(10));
+        let result = |entry, match_reason| QueryResult {
+            entry,
+            match_reason,
+            score: 0,
+        };
+        let ranked = rank(vec![
+            result(&spy, MatchReason::Meaning("dog".to_string())),
+            result(&rare_dog, MatchReason::Meaning("dog".to_string())),
+            result(&dog, MatchReason::Meaning("dog".to_string())),
+            result(&spy, MatchReason::Vocabulary("間者".to_string())),
+        ]);
+        let order: Vec<&DictionaryEntry> = ranked.iter().map(|result| result.entry).collect();
+        assert!(std::ptr::eq(order[0], &spy));
+        assert!(std::ptr::eq(order[1], &dog));
+        assert!(std::ptr::eq(order[2], &rare_dog));
+        assert!(ranked[0].score > ranked[1].score);
+        assert!(is_priority_tag("news1") && is_priority_tag("P") && !is_priority_tag("n"));
+    }
+}
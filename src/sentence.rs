@@ -5,18 +5,24 @@ use crate::DictionaryEntry;
 #[derive(Debug)]
 pub struct Sentence<'a> {
     pub raw: String,
-    pub decomposition: Option<Vec<ParsedWord<'a>>>,
+    pub decomposition: Vec<ParsedWord<'a>>,
+    pub translations: Vec<String>,
     id: u32,
 }
 
 impl<'a> Sentence<'a> {
-    pub fn create(raw: String, decomposition: Option<Vec<ParsedWord<'a>>>, id: u32) -> Self {
+    pub fn create(raw: String, decomposition: Vec<ParsedWord<'a>>, id: u32) -> Self {
         Sentence {
             raw,
             decomposition,
+            translations: Vec::new(),
             id,
         }
     }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -32,6 +38,10 @@ impl<'a> ParsedWord<'a> {
             interpretations,
         }
     }
+
+    pub fn interpretations(&self) -> &[&'a DictionaryEntry] {
+        &self.interpretations
+    }
 }
 
 impl<'a> fmt::Display for ParsedWord<'a> {
@@ -0,0 +1,142 @@
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+use crate::composite_dictionaries::Kanji;
+
+const SIMILAR_KANJI_COUNT: usize = 10;
+const MIN_SIMILARITY: f64 = 0.4;
+const MAX_STROKE_DIFFERENCE: u8 = 4;
+const STROKE_PENALTY: f64 = 0.05;
+const LOOKALIKE_MATCH: f64 = 0.75;
+
+// kradfile components that are different but easily confused, 化 stands for 亻
+const LOOKALIKE_COMPONENTS: [&[&str]; 9] = [
+    &["土", "士"],
+    &["一", "二"],
+    &["化", "彳"],
+    &["人", "入", "八"],
+    &["日", "曰", "目"],
+    &["刀", "力"],
+    &["冫", "氵"],
+    &["衣", "礼"],
+    &["干", "千"],
+];
+
+fn lookalikes(component: &str) -> &'static [&'static str] {
+    LOOKALIKE_COMPONENTS
+        .iter()
+        .find(|group| group.contains(&component))
+        .copied()
+        .unwrap_or_default()
+}
+
+fn matched_components(components: &[&str], other: &[&str]) -> f64 {
+    components
+        .iter()
+        .map(|component| {
+            if other.contains(component) {
+                1.0
+            } else if lookalikes(component)
+                .iter()
+                .any(|lookalike| other.contains(lookalike))
+            {
+                LOOKALIKE_MATCH
+            } else {
+                0.0
+            }
+        })
+        .sum()
+}
+
+/// Dice coefficient of the component sets of two kanji, counting look-alike
+/// components as partial matches, minus a penalty per differing stroke.
+pub fn similarity(components: &[&str], strokes: u8, other: &[&str], other_strokes: u8) -> f64 {
+    if components.is_empty() || other.is_empty() {
+        return 0.0;
+    }
+    let matched = matched_components(components, other) + matched_components(other, components);
+    let dice = matched / (components.len() + other.len()) as f64;
+    dice - STROKE_PENALTY * strokes.abs_diff(other_strokes) as f64
+}
+
+/// The most similar kanji for every kanji in `kanji_dicts`, in the same order.
+pub fn find_similar_kanji(kanji_dicts: &[Kanji]) -> Vec<Vec<String>> {
+    let components: Vec<Vec<&str>> = kanji_dicts
+        .iter()
+        .map(|kanji| kanji.radicals().collect())
+        .collect();
+    let mut by_component: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, components) in components.iter().enumerate() {
+        for component in components {
+            by_component.entry(component).or_default().push(index);
+        }
+    }
+    (0..kanji_dicts.len())
+        .into_par_iter()
+        .map(|index| {
+            let kanji = &kanji_dicts[index];
+            let mut candidates: Vec<usize> = components[index]
+                .iter()
+                .flat_map(|component| {
+                    std::iter::once(*component).chain(lookalikes(component).iter().copied())
+                })
+                .filter_map(|component| by_component.get(component))
+                .flatten()
+                .copied()
+                .filter(|&candidate| {
+                    candidate != index
+                        && kanji_dicts[candidate].strokes().abs_diff(kanji.strokes())
+                            <= MAX_STROKE_DIFFERENCE
+                })
+                .collect();
+            candidates.sort_unstable();
+            candidates.dedup();
+            let mut scored: Vec<(f64, &Kanji)> = candidates
+                .into_iter()
+                .map(|candidate| {
+                    let other = &kanji_dicts[candidate];
+                    let score = similarity(
+                        &components[index],
+                        kanji.strokes(),
+                        &components[candidate],
+                        other.strokes(),
+                    );
+                    (score, other)
+                })
+                .filter(|(score, _)| *score >= MIN_SIMILARITY)
+                .collect();
+            scored.sort_by(|(score_a, a), (score_b, b)| {
+                score_b
+                    .total_cmp(score_a)
+                    .then(b.frequency().cmp(&a.frequency()))
+            });
+            scored
+                .into_iter()
+                .take(SIMILAR_KANJI_COUNT)
+                .map(|(_, other)| other.kanji().to_string())
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similarity() {
+        let not_yet = ["｜", "二", "ハ", "木", "亠"];
+        let end = ["｜", "一", "ハ", "木", "亠"];
+        let serve = ["化", "寸", "土"];
+        let wait = ["寸", "土", "彳"];
+        let question = ["口", "門"];
+        let interval = ["日", "門"];
+        let moon = ["月"];
+        assert!(similarity(&not_yet, 5, &end, 5) > 0.9);
+        assert!(similarity(&serve, 8, &wait, 9) > 0.8);
+        assert!(similarity(&["土"], 3, &["士"], 3) >= MIN_SIMILARITY);
+        assert!(similarity(&question, 11, &interval, 12) >= MIN_SIMILARITY);
+        assert!(similarity(&["日"], 4, &moon, 4) < MIN_SIMILARITY);
+        assert_eq!(similarity(&[], 4, &moon, 4), 0.0);
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use crate::{
+    basic_dictionaries::{Innocent, Jmdict, Kanjidic, Tag},
+    composite_dictionaries::{DictionaryEntry, Kanji, Word},
+};
+
+/// Tags that describe usage rather than the part of speech.
+const USAGE_TAGS: [&str; 3] = ["uk", "col", "pol"];
+
+pub fn word(vocabulary: &str, reading: &str, tags: &[&str], frequency: Option<u32>) -> Word {
+    word_with(vocabulary, reading, tags, &[], frequency)
+}
+
+pub fn word_entry(
+    vocabulary: &str,
+    reading: &str,
+    tags: &[&str],
+    frequency: Option<u32>,
+) -> DictionaryEntry {
+    DictionaryEntry::Word(word(vocabulary, reading, tags, frequency))
+}
+
+pub fn meanings_entry(
+    vocabulary: &str,
+    meanings: &[&str],
+    frequency: Option<u32>,
+) -> DictionaryEntry {
+    DictionaryEntry::Word(word_with(vocabulary, "", &[], meanings, frequency))
+}
+
+fn word_with(
+    vocabulary: &str,
+    reading: &str,
+    tags: &[&str],
+    meanings: &[&str],
+    frequency: Option<u32>,
+) -> Word {
+    let jmdict = Jmdict {
+        vocabulary: vocabulary.to_string(),
+        reading: reading.to_string(),
+        romaji: String::new(),
+        tags: tags
+            .iter()
+            .map(|&tag| {
+                let category = if USAGE_TAGS.contains(&tag) {
+                    ""
+                } else {
+                    "partOfSpeech"
+                };
+                (tag.to_string(), Tag::new(tag, category))
+            })
+            .collect(),
+        meanings: meanings.iter().map(|meaning| meaning.to_string()).collect(),
+        id: 0,
+    };
+    let innocent = frequency.map(|frequency| Innocent {
+        vocabulary: vocabulary.to_string(),
+        frequency,
+    });
+    Word::from(jmdict, innocent.as_ref(), None)
+}
+
+pub fn kanji(kanji: &str, on_yomi: &str, kun_yomi: &str, attributes: &[(&str, &str)]) -> Kanji {
+    let mut attributes: HashMap<String, String> = attributes
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    attributes
+        .entry("strokes".to_string())
+        .or_insert_with(|| "1".to_string());
+    attributes.insert(
+        "ucs".to_string(),
+        format!("{:x}", kanji.chars().next().unwrap() as u32),
+    );
+    let kanjidic = Kanjidic {
+        kanji: kanji.to_string(),
+        kun_yomi: kun_yomi.to_string(),
+        on_yomi: on_yomi.to_string(),
+        tags: HashMap::new(),
+        meanings: Vec::new(),
+        attributes,
+    };
+    Kanji::from(kanjidic, None, None)
+}
+
+pub fn kanji_entry(character: &str, attributes: &[(&str, &str)]) -> DictionaryEntry {
+    DictionaryEntry::Kanji(kanji(character, "", "", attributes))
+}
@@ -0,0 +1,359 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::cmp::min;
+
+use crate::{
+    deinflection::may_be_inflected, query::MatchReason, sentence::ParsedWord, Dictionary,
+    DictionaryEntry,
+};
+
+lazy_static! {
+    pub static ref NON_WORD_CHARS: Regex =
+        Regex::new(r"[a-zA-Z\d・、。！？「」『』・〜（）《》〈〉【】　]+").unwrap();
+    static ref HIRAGANA: Regex = Regex::new(r"^\p{Hiragana}+$").unwrap();
+    static ref KATAKANA: Regex = Regex::new(r"^[\p{Katakana}ー]+$").unwrap();
+}
+
+// longest word is a name 42 characters long
+const LONGEST_POSSIBLE_WORD: usize = 42;
+// conjugations longer than this aren't deinflected
+const LONGEST_CONJUGATION: usize = 20;
+
+// costs are roughly -ln(frequency / corpus size)
+const MAX_WORD_COST: f64 = 20.0;
+const UNKNOWN_FREQUENCY_COST: f64 = 21.0;
+const NAME_COST: f64 = 17.0;
+const PARTICLE_COST: f64 = 6.0;
+const READING_PENALTY: f64 = 5.0;
+const CONJUGATION_PENALTY: f64 = 0.3;
+const UNKNOWN_COST: f64 = 30.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PartOfSpeech {
+    Noun,
+    Verb,
+    Adjective,
+    Particle,
+    Auxiliary,
+    Prefix,
+    Suffix,
+    Name,
+    Symbol,
+    Unknown,
+}
+
+impl PartOfSpeech {
+    fn of(entry: &DictionaryEntry) -> PartOfSpeech {
+        let word = match entry {
+            DictionaryEntry::Word(word) => word,
+            DictionaryEntry::Name(_) => return PartOfSpeech::Name,
+            _ => return PartOfSpeech::Unknown,
+        };
+        let tags: Vec<&str> = word.tags().collect();
+        let has = |pattern: &dyn Fn(&str) -> bool| tags.iter().any(|&tag| pattern(tag));
+        if has(&|tag| tag == "prt") {
+            PartOfSpeech::Particle
+        } else if has(&|tag| matches!(tag, "aux" | "aux-v" | "aux-adj" | "cop")) {
+            PartOfSpeech::Auxiliary
+        } else if has(&|tag| tag == "pref") {
+            PartOfSpeech::Prefix
+        } else if has(&|tag| matches!(tag, "suf" | "ctr")) {
+            PartOfSpeech::Suffix
+        } else if has(&|tag| tag.starts_with('v') && tag != "vs") {
+            PartOfSpeech::Verb
+        } else if has(&|tag| matches!(tag, "adj-i" | "adj-ix")) {
+            PartOfSpeech::Adjective
+        } else {
+            PartOfSpeech::Noun
+        }
+    }
+}
+
+/// Cost of putting `right` after `left`, `None` marks the sentence boundaries.
+fn connection_cost(left: Option<PartOfSpeech>, right: Option<PartOfSpeech>) -> f64 {
+    use PartOfSpeech::*;
+    match (left, right) {
+        (None | Some(Symbol), Some(Particle | Auxiliary | Suffix)) => 2.0,
+        (Some(Prefix), Some(Noun | Name | Suffix)) => -1.0,
+        (Some(Prefix), _) => 2.0,
+        (Some(Noun | Name), Some(Suffix)) => -1.0,
+        (_, Some(Suffix)) => 2.0,
+        (Some(Noun | Name), Some(Particle)) => -1.0,
+        (Some(Verb | Adjective), Some(Particle | Auxiliary)) => -0.5,
+        (Some(Particle), Some(Particle)) => 1.0,
+        _ => 0.0,
+    }
+}
+
+struct Node<'a> {
+    start: usize,
+    end: usize,
+    interpretations: Vec<&'a DictionaryEntry>,
+    cost: f64,
+    part_of_speech: PartOfSpeech,
+}
+
+fn entry_cost(result: &(&DictionaryEntry, MatchReason)) -> f64 {
+    let (entry, match_reason) = result;
+    let cost = match (entry, entry.frequency()) {
+        (DictionaryEntry::Name(_), _) => NAME_COST,
+        (_, Some(frequency)) => MAX_WORD_COST - (frequency as f64).ln(),
+        // the frequency list was built from segmented text without particles
+        _ if PartOfSpeech::of(entry) == PartOfSpeech::Particle => PARTICLE_COST,
+        _ => UNKNOWN_FREQUENCY_COST,
+    };
+    let matched_reading = match match_reason {
+        MatchReason::Reading(_) => true,
+        MatchReason::Conjugation { base, .. } => match entry {
+            DictionaryEntry::Word(word) => word.vocabulary != *base,
+            _ => false,
+        },
+        _ => false,
+    };
+    // words that are usually written with kanji rarely show up in kana
+    let reading_penalty = match entry {
+        DictionaryEntry::Word(word) if word.tags().any(|tag| tag == "uk") => 0.0,
+        _ if matched_reading => READING_PENALTY,
+        _ => 0.0,
+    };
+    let conjugation_penalty = match match_reason {
+        MatchReason::Conjugation {
+            grammatical_forms, ..
+        } => CONJUGATION_PENALTY * grammatical_forms.len() as f64,
+        _ => 0.0,
+    };
+    cost + reading_penalty + conjugation_penalty
+}
+
+fn lookup<'a>(dict: &Dictionary<'a>, surface: &str) -> Vec<(&'a DictionaryEntry, MatchReason)> {
+    let mut results: Vec<(&'a DictionaryEntry, MatchReason)> = dict
+        .get(surface)
+        .into_iter()
+        .filter(|entry| matches!(entry, DictionaryEntry::Word(_) | DictionaryEntry::Name(_)))
+        .map(|entry| (entry, entry.match_reason(surface)))
+        .filter(|(_, match_reason)| {
+            matches!(
+                match_reason,
+                MatchReason::Vocabulary(_) | MatchReason::Reading(_)
+            )
+        })
+        .collect();
+    let ends_in_kana = surface
+        .chars()
+        .last()
+        .is_some_and(|c| HIRAGANA.is_match(&c.to_string()));
+    if ends_in_kana && surface.chars().count() <= LONGEST_CONJUGATION && may_be_inflected(surface) {
+        for result in dict.get_deinflected(surface) {
+            if !results
+                .iter()
+                .any(|(entry, _)| std::ptr::eq(*entry, result.entry))
+            {
+                results.push((result.entry, result.match_reason));
+            }
+        }
+    }
+    results.sort_by(|a, b| {
+        entry_cost(a)
+            .total_cmp(&entry_cost(b))
+            .then(a.0.id().cmp(&b.0.id()))
+    });
+    results
+}
+
+fn build_lattice<'a>(dict: &Dictionary<'a>, chars: &[char]) -> Vec<Node<'a>> {
+    let mut nodes: Vec<Node<'a>> = Vec::new();
+    let is_symbol: Vec<bool> = chars
+        .iter()
+        .map(|c| NON_WORD_CHARS.is_match(&c.to_string()))
+        .collect();
+    let mut start = 0;
+    while start < chars.len() {
+        if is_symbol[start] {
+            let end = (start..chars.len())
+                .find(|&i| !is_symbol[i])
+                .unwrap_or(chars.len());
+            nodes.push(Node {
+                start,
+                end,
+                interpretations: Vec::new(),
+                cost: 0.0,
+                part_of_speech: PartOfSpeech::Symbol,
+            });
+            start = end;
+            continue;
+        }
+        let longest_end = min(chars.len(), start + LONGEST_POSSIBLE_WORD);
+        for end in start + 1..=longest_end {
+            if is_symbol[end - 1] {
+                break;
+            }
+            let surface: String = chars[start..end].iter().collect();
+            let results = lookup(dict, &surface);
+            if let Some(best) = results.first() {
+                nodes.push(Node {
+                    start,
+                    end,
+                    cost: entry_cost(best),
+                    part_of_speech: PartOfSpeech::of(best.0),
+                    interpretations: results.into_iter().map(|(entry, _)| entry).collect(),
+                });
+            }
+        }
+        // unknown katakana runs are most likely loanwords or foreign names
+        let katakana_end = (start..longest_end)
+            .find(|&i| !KATAKANA.is_match(&chars[i].to_string()))
+            .unwrap_or(longest_end);
+        if katakana_end > start + 1 {
+            nodes.push(Node {
+                start,
+                end: katakana_end,
+                interpretations: Vec::new(),
+                cost: UNKNOWN_COST,
+                part_of_speech: PartOfSpeech::Unknown,
+            });
+        }
+        nodes.push(Node {
+            start,
+            end: start + 1,
+            interpretations: Vec::new(),
+            cost: UNKNOWN_COST,
+            part_of_speech: PartOfSpeech::Unknown,
+        });
+        start += 1;
+    }
+    nodes
+}
+
+/// Splits `sentence` into words by building a lattice of every dictionary
+/// match, including names and conjugated forms, and picking the cheapest path
+/// through it. Characters that aren't covered by any entry become words
+/// without interpretations.
+pub fn tokenize<'a>(dict: &Dictionary<'a>, sentence: &str) -> Vec<ParsedWord<'a>> {
+    let chars: Vec<char> = sentence.chars().collect();
+    let mut nodes = build_lattice(dict, &chars);
+    nodes.sort_by_key(|node| (node.start, node.end));
+    // cheapest path cost to the end of each node and the node before it
+    let mut best: Vec<(f64, Option<usize>)> = Vec::with_capacity(nodes.len());
+    // nodes by the position they end at, every node before one starting
+    // there has been visited already
+    let mut ending_at: Vec<Vec<usize>> = vec![Vec::new(); chars.len() + 1];
+    for (index, node) in nodes.iter().enumerate() {
+        let from_start = match node.start {
+            0 => Some((connection_cost(None, Some(node.part_of_speech)), None)),
+            _ => None,
+        };
+        let from_previous = ending_at[node.start]
+            .iter()
+            .filter(|&&previous| best[previous].0.is_finite())
+            .map(|&previous| {
+                let cost = best[previous].0
+                    + connection_cost(
+                        Some(nodes[previous].part_of_speech),
+                        Some(node.part_of_speech),
+                    );
+                (cost, Some(previous))
+            });
+        let (cost, previous) = from_start
+            .into_iter()
+            .chain(from_previous)
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .unwrap_or((f64::INFINITY, None));
+        best.push((cost + node.cost, previous));
+        ending_at[node.end].push(index);
+    }
+    let last = ending_at[chars.len()].iter().copied().min_by(|&a, &b| {
+        let cost_a = best[a].0 + connection_cost(Some(nodes[a].part_of_speech), None);
+        let cost_b = best[b].0 + connection_cost(Some(nodes[b].part_of_speech), None);
+        cost_a.total_cmp(&cost_b)
+    });
+    let mut path: Vec<usize> = Vec::new();
+    let mut current = last;
+    while let Some(index) = current {
+        path.push(index);
+        current = best[index].1;
+    }
+    let mut nodes: Vec<Option<Node<'a>>> = nodes.into_iter().map(Some).collect();
+    path.into_iter()
+        .rev()
+        .filter_map(|index| nodes[index].take())
+        .map(|node| {
+            ParsedWord::create(
+                chars[node.start..node.end].iter().collect(),
+                node.interpretations,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::word_entry;
+
+    fn words(dict: &Dictionary, sentence: &str) -> Vec<String> {
+        tokenize(dict, sentence)
+            .into_iter()
+            .map(|parsed| parsed.word)
+            .collect()
+    }
+
+    #[test]
+    fn test_hiragana_remnants() {
+        let entries = vec![
+            word_entry("此れ", "これ", &["pn", "uk"], Some(300000)),
+            word_entry("猫", "ねこ", &["n"], Some(20000)),
+            word_entry("本", "ほん", &["n"], Some(50000)),
+            word_entry("読む", "よむ", &["v5m", "vt"], Some(20000)),
+            word_entry("は", "は", &["prt"], Some(900000)),
+            word_entry("か", "か", &["prt"], Some(600000)),
+            word_entry("を", "を", &["prt"], Some(900000)),
+            word_entry("です", "です", &["cop"], Some(500000)),
+        ];
+        let dict = Dictionary::create(&entries);
+        assert_eq!(
+            words(&dict, "これはねこですか"),
+            ["これ", "は", "ねこ", "です", "か"]
+        );
+        let tokens = tokenize(&dict, "ほんをよみました");
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| token.word.as_str())
+                .collect::<Vec<_>>(),
+            ["ほん", "を", "よみました"]
+        );
+        match tokens[2].interpretations()[0] {
+            DictionaryEntry::Word(word) => assert_eq!(word.vocabulary, "読む"),
+            _ => panic!("よみました should be read as a word"),
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let entries = vec![
+            word_entry("東", "ひがし", &["n"], Some(3000)),
+            word_entry("東京", "とうきょう", &["n"], Some(50000)),
+            word_entry("京都", "きょうと", &["n"], Some(30000)),
+            word_entry("東京都", "とうきょうと", &["n"], Some(5000)),
+            word_entry("都", "と", &["n", "suf"], Some(2000)),
+            word_entry("行く", "いく", &["v5k-s", "vi"], Some(80000)),
+            word_entry("に", "に", &["prt"], Some(900000)),
+            word_entry("で", "で", &["prt"], Some(800000)),
+        ];
+        let dict = Dictionary::create(&entries);
+        assert_eq!(
+            words(&dict, "東京都に行きました"),
+            ["東京都", "に", "行きました"]
+        );
+        assert_eq!(words(&dict, "京都で"), ["京都", "で"]);
+        let tokens = tokenize(&dict, "東京でテスト。");
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| token.word.as_str())
+                .collect::<Vec<_>>(),
+            ["東京", "で", "テスト", "。"]
+        );
+        assert!(tokens[2].interpretations().is_empty());
+    }
+}
@@ -1,398 +1,959 @@
+use anyhow::Result;
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::{collections::HashSet, error::Error, fmt};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GrammaticalForm {
     Adverbial,
+    Appearance,
+    Attempt,
+    Attributive,
     Causative,
+    Completive,
     Conditional,
     Desire,
+    Excessive,
+    Honorific,
+    Humble,
     Imperative,
     MasuStem,
     Negative,
+    Nominal,
     Passive,
     Past,
     Polite,
     Potential,
+    Predicative,
+    Preparatory,
+    Progressive,
     ProvisionalConditional,
     Short,
     TeForm,
     Volitional,
 }
 
+impl fmt::Display for GrammaticalForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            GrammaticalForm::Adverbial => "adverbial",
+            GrammaticalForm::Appearance => "appearance",
+            GrammaticalForm::Attempt => "attempt",
+            GrammaticalForm::Attributive => "attributive",
+            GrammaticalForm::Causative => "causative",
+            GrammaticalForm::Completive => "completive",
+            GrammaticalForm::Conditional => "conditional",
+            GrammaticalForm::Desire => "desire",
+            GrammaticalForm::Excessive => "excessive",
+            GrammaticalForm::Honorific => "honorific",
+            GrammaticalForm::Humble => "humble",
+            GrammaticalForm::Imperative => "imperative",
+            GrammaticalForm::MasuStem => "masu stem",
+            GrammaticalForm::Negative => "negative",
+            GrammaticalForm::Nominal => "nominal",
+            GrammaticalForm::Passive => "passive",
+            GrammaticalForm::Past => "past",
+            GrammaticalForm::Polite => "polite",
+            GrammaticalForm::Potential => "potential",
+            GrammaticalForm::Predicative => "predicative",
+            GrammaticalForm::Preparatory => "preparatory",
+            GrammaticalForm::Progressive => "progressive",
+            GrammaticalForm::ProvisionalConditional => "provisional conditional",
+            GrammaticalForm::Short => "short",
+            GrammaticalForm::TeForm => "te-form",
+            GrammaticalForm::Volitional => "volitional",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ConjugatedWord {
     pub kanji_form: String,
     pub kana_form: String,
     pub grammatical_forms: Vec<GrammaticalForm>,
+    /// Mora after which the pitch drops in the Tokyo accent, 0 if it never drops
+    pub pitch_accent: Option<usize>,
+}
+
+impl ConjugatedWord {
+    pub fn new(
+        kanji_form: String,
+        kana_form: String,
+        grammatical_forms: Vec<GrammaticalForm>,
+    ) -> ConjugatedWord {
+        ConjugatedWord {
+            kanji_form,
+            kana_form,
+            grammatical_forms,
+            pitch_accent: None,
+        }
+    }
 }
 
 pub fn generate_all_verb_conjugations(
     verb_kanji_form: &str,
     verb_reading_form: &str,
     tags: Vec<&String>,
-) -> Option<Vec<ConjugatedWord>> {
+) -> Result<Option<Vec<ConjugatedWord>>> {
     if !tags.iter().any(|s| s.starts_with('v')) || verb_kanji_form.is_empty() {
-        None
-    } else if let Some(category) = tags.into_iter().find(|&tag| VERB_TAGS.contains(tag)) {
-        let verb_kana_form = match verb_reading_form {
-            "" => verb_kanji_form,
-            _ => verb_reading_form,
-        };
-        Some(vec![
-            // plain forms
-            ConjugatedWord {
-                kanji_form: masu_stem(verb_kanji_form, category, false),
-                kana_form: masu_stem(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::MasuStem],
-            },
-            ConjugatedWord {
-                kanji_form: causative(verb_kanji_form, category, false),
-                kana_form: causative(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Causative],
-            },
-            ConjugatedWord {
-                kanji_form: causative_passive(verb_kanji_form, category, false),
-                kana_form: causative_passive(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Causative, GrammaticalForm::Passive],
-            },
-            ConjugatedWord {
-                kanji_form: causative_short(verb_kanji_form, category, false),
-                kana_form: causative_short(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Causative, GrammaticalForm::Short],
-            },
-            ConjugatedWord {
-                kanji_form: conditional(verb_kanji_form, category, false),
-                kana_form: conditional(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Conditional],
-            },
-            ConjugatedWord {
-                kanji_form: desire(verb_kanji_form, category, false),
-                kana_form: desire(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Desire],
-            },
-            ConjugatedWord {
-                kanji_form: imperative(verb_kanji_form, category, false),
-                kana_form: imperative(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Imperative],
-            },
-            ConjugatedWord {
-                kanji_form: passive(verb_kanji_form, category, false),
-                kana_form: passive(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Passive],
-            },
-            ConjugatedWord {
-                kanji_form: potential(verb_kanji_form, category, false),
-                kana_form: potential(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Potential],
-            },
-            ConjugatedWord {
-                kanji_form: provisional_conditional(verb_kanji_form, category, false),
-                kana_form: provisional_conditional(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::ProvisionalConditional],
-            },
-            ConjugatedWord {
-                kanji_form: volitional(verb_kanji_form, category, false),
-                kana_form: volitional(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Volitional],
-            },
-            // Negative forms
-            ConjugatedWord {
-                kanji_form: negative(verb_kanji_form, category, false),
-                kana_form: negative(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Negative],
-            },
-            ConjugatedWord {
-                kanji_form: causative_negative(verb_kanji_form, category, false),
-                kana_form: causative_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Causative, GrammaticalForm::Negative],
-            },
-            ConjugatedWord {
-                kanji_form: causative_passive_negative(verb_kanji_form, category, false),
-                kana_form: causative_passive_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Causative,
-                    GrammaticalForm::Passive,
-                    GrammaticalForm::Negative,
-                ],
-            },
-            ConjugatedWord {
-                kanji_form: conditional_negative(verb_kanji_form, category, false),
-                kana_form: conditional_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Conditional, GrammaticalForm::Negative],
-            },
-            ConjugatedWord {
-                kanji_form: desire_negative(verb_kanji_form, category, false),
-                kana_form: desire_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Desire, GrammaticalForm::Negative],
-            },
-            ConjugatedWord {
-                kanji_form: imperative_negative(verb_kanji_form, category, false),
-                kana_form: imperative_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Imperative, GrammaticalForm::Negative],
-            },
-            ConjugatedWord {
-                kanji_form: passive_negative(verb_kanji_form, category, false),
-                kana_form: passive_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Passive, GrammaticalForm::Negative],
-            },
-            ConjugatedWord {
-                kanji_form: potential_negative(verb_kanji_form, category, false),
-                kana_form: potential_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Potential, GrammaticalForm::Negative],
-            },
-            // Past forms
-            ConjugatedWord {
-                kanji_form: past(verb_kanji_form, category, false),
-                kana_form: past(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Past],
-            },
-            ConjugatedWord {
-                kanji_form: causative_past(verb_kanji_form, category, false),
-                kana_form: causative_past(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Causative, GrammaticalForm::Past],
-            },
-            ConjugatedWord {
-                kanji_form: causative_passive_past(verb_kanji_form, category, false),
-                kana_form: causative_passive_past(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Causative,
-                    GrammaticalForm::Passive,
-                    GrammaticalForm::Past,
-                ],
-            },
-            ConjugatedWord {
-                kanji_form: desire_past(verb_kanji_form, category, false),
-                kana_form: desire_past(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Desire, GrammaticalForm::Past],
-            },
-            ConjugatedWord {
-                kanji_form: passive_past(verb_kanji_form, category, false),
-                kana_form: passive_past(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Passive, GrammaticalForm::Past],
-            },
-            ConjugatedWord {
-                kanji_form: potential_past(verb_kanji_form, category, false),
-                kana_form: potential_past(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Potential, GrammaticalForm::Past],
-            },
-            // Past negative forms
-            ConjugatedWord {
-                kanji_form: past_negative(verb_kanji_form, category, false),
-                kana_form: past_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Past, GrammaticalForm::Negative],
-            },
-            ConjugatedWord {
-                kanji_form: causative_past_negative(verb_kanji_form, category, false),
-                kana_form: causative_past_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Causative,
-                    GrammaticalForm::Past,
-                    GrammaticalForm::Negative,
-                ],
-            },
-            ConjugatedWord {
-                kanji_form: causative_passive_past_negative(verb_kanji_form, category, false),
-                kana_form: causative_passive_past_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Causative,
-                    GrammaticalForm::Passive,
-                    GrammaticalForm::Past,
-                    GrammaticalForm::Negative,
-                ],
-            },
-            ConjugatedWord {
-                kanji_form: desire_past_negative(verb_kanji_form, category, false),
-                kana_form: desire_past_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Desire,
-                    GrammaticalForm::Past,
-                    GrammaticalForm::Negative,
-                ],
-            },
-            ConjugatedWord {
-                kanji_form: passive_past_negative(verb_kanji_form, category, false),
-                kana_form: passive_past_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Passive,
-                    GrammaticalForm::Past,
-                    GrammaticalForm::Negative,
-                ],
-            },
-            ConjugatedWord {
-                kanji_form: potential_past_negative(verb_kanji_form, category, false),
-                kana_form: potential_past_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Potential,
-                    GrammaticalForm::Past,
-                    GrammaticalForm::Negative,
-                ],
-            },
-            // Te-forms
-            ConjugatedWord {
-                kanji_form: te_form(verb_kanji_form, category, false),
-                kana_form: te_form(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::TeForm],
-            },
-            ConjugatedWord {
-                kanji_form: causative_te(verb_kanji_form, category, false),
-                kana_form: causative_te(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Causative, GrammaticalForm::TeForm],
-            },
-            ConjugatedWord {
-                kanji_form: causative_passive_te(verb_kanji_form, category, false),
-                kana_form: causative_passive_te(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Causative,
-                    GrammaticalForm::Passive,
-                    GrammaticalForm::TeForm,
-                ],
-            },
-            ConjugatedWord {
-                kanji_form: passive_te(verb_kanji_form, category, false),
-                kana_form: passive_te(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Passive, GrammaticalForm::TeForm],
-            },
-            ConjugatedWord {
-                kanji_form: potential_te(verb_kanji_form, category, false),
-                kana_form: potential_te(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Potential, GrammaticalForm::TeForm],
-            },
-            // Polite forms
-            ConjugatedWord {
-                kanji_form: polite(verb_kanji_form, category, false),
-                kana_form: polite(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Polite],
-            },
-            ConjugatedWord {
-                kanji_form: causative_polite(verb_kanji_form, category, false),
-                kana_form: causative_polite(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Causative, GrammaticalForm::Polite],
-            },
-            ConjugatedWord {
-                kanji_form: causative_passive_polite(verb_kanji_form, category, false),
-                kana_form: causative_passive_polite(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Causative,
-                    GrammaticalForm::Passive,
-                    GrammaticalForm::Polite,
-                ],
-            },
-            ConjugatedWord {
-                kanji_form: passive_polite(verb_kanji_form, category, false),
-                kana_form: passive_polite(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Passive, GrammaticalForm::Polite],
-            },
-            ConjugatedWord {
-                kanji_form: potential_polite(verb_kanji_form, category, false),
-                kana_form: potential_polite(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Potential, GrammaticalForm::Polite],
-            },
-            ConjugatedWord {
-                kanji_form: volitional_polite(verb_kanji_form, category, false),
-                kana_form: volitional_polite(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Volitional, GrammaticalForm::Polite],
-            },
-            // Polite negative forms
-            ConjugatedWord {
-                kanji_form: polite_negative(verb_kanji_form, category, false),
-                kana_form: polite_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Polite, GrammaticalForm::Negative],
-            },
-            ConjugatedWord {
-                kanji_form: causative_polite_negative(verb_kanji_form, category, false),
-                kana_form: causative_polite_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Causative,
-                    GrammaticalForm::Polite,
-                    GrammaticalForm::Negative,
-                ],
-            },
-            ConjugatedWord {
-                kanji_form: causative_passive_polite_negative(verb_kanji_form, category, false),
-                kana_form: causative_passive_polite_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Causative,
-                    GrammaticalForm::Passive,
-                    GrammaticalForm::Polite,
-                    GrammaticalForm::Negative,
-                ],
-            },
-            ConjugatedWord {
-                kanji_form: passive_polite_negative(verb_kanji_form, category, false),
-                kana_form: passive_polite_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Passive,
-                    GrammaticalForm::Polite,
-                    GrammaticalForm::Negative,
-                ],
-            },
-            ConjugatedWord {
-                kanji_form: potential_polite_negative(verb_kanji_form, category, false),
-                kana_form: potential_polite_negative(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Potential,
-                    GrammaticalForm::Polite,
-                    GrammaticalForm::Negative,
-                ],
-            },
-            // Polite past forms
-            ConjugatedWord {
-                kanji_form: polite_past(verb_kanji_form, category, false),
-                kana_form: polite_past(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Polite, GrammaticalForm::Past],
-            },
-            ConjugatedWord {
-                kanji_form: causative_polite_past(verb_kanji_form, category, false),
-                kana_form: causative_polite_past(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Causative,
-                    GrammaticalForm::Polite,
-                    GrammaticalForm::Past,
-                ],
-            },
-            ConjugatedWord {
-                kanji_form: causative_passive_polite_past(verb_kanji_form, category, false),
-                kana_form: causative_passive_polite_past(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Causative,
-                    GrammaticalForm::Passive,
-                    GrammaticalForm::Polite,
-                    GrammaticalForm::Past,
-                ],
-            },
-            ConjugatedWord {
-                kanji_form: passive_polite_past(verb_kanji_form, category, false),
-                kana_form: passive_polite_past(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Passive,
-                    GrammaticalForm::Polite,
-                    GrammaticalForm::Past,
-                ],
-            },
-            ConjugatedWord {
-                kanji_form: potential_polite_past(verb_kanji_form, category, false),
-                kana_form: potential_polite_past(verb_kana_form, category, true),
-                grammatical_forms: vec![
-                    GrammaticalForm::Potential,
-                    GrammaticalForm::Polite,
-                    GrammaticalForm::Past,
-                ],
-            },
-            // Polite te-form
-            ConjugatedWord {
-                kanji_form: polite_te_form(verb_kanji_form, category, false),
-                kana_form: polite_te_form(verb_kana_form, category, true),
-                grammatical_forms: vec![GrammaticalForm::Polite, GrammaticalForm::TeForm],
-            },
-        ])
-    } else {
-        None
+        return Ok(None);
+    }
+    // tags come from a map, so words with several verb classes need a fixed
+    // choice, classical and alternative classes come last, e.g. v5s for 熟す
+    // over its vs-c
+    let Some(category) = tags
+        .into_iter()
+        .filter(|tag| is_verb_category(tag))
+        .min_by_key(|tag| {
+            let secondary = matches!(tag.as_str(), "vn" | "vr" | "vs-c")
+                || tag.starts_with("v2")
+                || tag.starts_with("v4");
+            (secondary, tag.as_str())
+        })
+    else {
+        return Ok(None);
+    };
+    let verb_kana_form = match verb_reading_form {
+        "" => verb_kanji_form,
+        _ => verb_reading_form,
+    };
+    let conjugations = match category.as_str() {
+        "vs" => {
+            let suru_kanji_form = [verb_kanji_form, "する"].concat();
+            let suru_kana_form = [verb_kana_form, "する"].concat();
+            let mut conjugations = conjugate_verb(&suru_kanji_form, &suru_kana_form, "vs-i")?;
+            conjugations.extend(keigo_conjugations(
+                &suru_kanji_form,
+                &suru_kana_form,
+                category,
+            )?);
+            conjugations.extend(auxiliary_conjugations(
+                &suru_kanji_form,
+                &suru_kana_form,
+                "vs-i",
+            )?);
+            conjugations.insert(
+                0,
+                ConjugatedWord::new(suru_kanji_form, suru_kana_form, Vec::new()),
+            );
+            conjugations
+        }
+        "vs-c" => conjugate_verb(verb_kanji_form, verb_kana_form, "v5s")?,
+        "vn" => conjugate_verb(verb_kanji_form, verb_kana_form, "v5n")?,
+        "vr" => conjugate_classical_verb(verb_kanji_form, verb_kana_form, category)?,
+        _ if category.starts_with("v2") || category.starts_with("v4") => {
+            conjugate_classical_verb(verb_kanji_form, verb_kana_form, category)?
+        }
+        _ => {
+            let mut conjugations = conjugate_verb(verb_kanji_form, verb_kana_form, category)?;
+            conjugations.extend(keigo_conjugations(
+                verb_kanji_form,
+                verb_kana_form,
+                category,
+            )?);
+            conjugations.extend(auxiliary_conjugations(
+                verb_kanji_form,
+                verb_kana_form,
+                category,
+            )?);
+            conjugations
+        }
+    };
+    Ok(Some(conjugations))
+}
+
+pub struct SuppletiveVerb {
+    pub plain: &'static str,
+    pub plain_reading: &'static str,
+    pub plain_category: &'static str,
+    pub keigo: &'static str,
+    pub keigo_reading: &'static str,
+    pub keigo_category: &'static str,
+    pub form: GrammaticalForm,
+}
+
+impl SuppletiveVerb {
+    const fn new(
+        (plain, plain_reading, plain_category): (&'static str, &'static str, &'static str),
+        (keigo, keigo_reading, keigo_category): (&'static str, &'static str, &'static str),
+        form: GrammaticalForm,
+    ) -> SuppletiveVerb {
+        SuppletiveVerb {
+            plain,
+            plain_reading,
+            plain_category,
+            keigo,
+            keigo_reading,
+            keigo_category,
+            form,
+        }
+    }
+}
+
+pub const SUPPLETIVE_VERBS: [SuppletiveVerb; 26] = [
+    SuppletiveVerb::new(
+        ("行く", "いく", "v5k-s"),
+        ("いらっしゃる", "いらっしゃる", "v5aru"),
+        GrammaticalForm::Honorific,
+    ),
+    SuppletiveVerb::new(
+        ("行く", "いく", "v5k-s"),
+        ("参る", "まいる", "v5r"),
+        GrammaticalForm::Humble,
+    ),
+    SuppletiveVerb::new(
+        ("来る", "くる", "vk"),
+        ("いらっしゃる", "いらっしゃる", "v5aru"),
+        GrammaticalForm::Honorific,
+    ),
+    SuppletiveVerb::new(
+        ("来る", "くる", "vk"),
+        ("参る", "まいる", "v5r"),
+        GrammaticalForm::Humble,
+    ),
+    SuppletiveVerb::new(
+        ("居る", "いる", "v1"),
+        ("いらっしゃる", "いらっしゃる", "v5aru"),
+        GrammaticalForm::Honorific,
+    ),
+    SuppletiveVerb::new(
+        ("居る", "いる", "v1"),
+        ("おる", "おる", "v5r"),
+        GrammaticalForm::Humble,
+    ),
+    SuppletiveVerb::new(
+        ("食べる", "たべる", "v1"),
+        ("召し上がる", "めしあがる", "v5r"),
+        GrammaticalForm::Honorific,
+    ),
+    SuppletiveVerb::new(
+        ("食べる", "たべる", "v1"),
+        ("頂く", "いただく", "v5k"),
+        GrammaticalForm::Humble,
+    ),
+    SuppletiveVerb::new(
+        ("飲む", "のむ", "v5m"),
+        ("召し上がる", "めしあがる", "v5r"),
+        GrammaticalForm::Honorific,
+    ),
+    SuppletiveVerb::new(
+        ("飲む", "のむ", "v5m"),
+        ("頂く", "いただく", "v5k"),
+        GrammaticalForm::Humble,
+    ),
+    SuppletiveVerb::new(
+        ("言う", "いう", "v5u"),
+        ("おっしゃる", "おっしゃる", "v5aru"),
+        GrammaticalForm::Honorific,
+    ),
+    SuppletiveVerb::new(
+        ("言う", "いう", "v5u"),
+        ("申す", "もうす", "v5s"),
+        GrammaticalForm::Humble,
+    ),
+    SuppletiveVerb::new(
+        ("為る", "する", "vs-i"),
+        ("為さる", "なさる", "v5aru"),
+        GrammaticalForm::Honorific,
+    ),
+    SuppletiveVerb::new(
+        ("為る", "する", "vs-i"),
+        ("致す", "いたす", "v5s"),
+        GrammaticalForm::Humble,
+    ),
+    SuppletiveVerb::new(
+        ("見る", "みる", "v1"),
+        ("ご覧になる", "ごらんになる", "v5r"),
+        GrammaticalForm::Honorific,
+    ),
+    SuppletiveVerb::new(
+        ("見る", "みる", "v1"),
+        ("拝見する", "はいけんする", "vs-i"),
+        GrammaticalForm::Humble,
+    ),
+    SuppletiveVerb::new(
+        ("知る", "しる", "v5r"),
+        ("存じる", "ぞんじる", "v1"),
+        GrammaticalForm::Humble,
+    ),
+    SuppletiveVerb::new(
+        ("思う", "おもう", "v5u"),
+        ("存じる", "ぞんじる", "v1"),
+        GrammaticalForm::Humble,
+    ),
+    SuppletiveVerb::new(
+        ("呉れる", "くれる", "v1-s"),
+        ("下さる", "くださる", "v5aru"),
+        GrammaticalForm::Honorific,
+    ),
+    SuppletiveVerb::new(
+        ("貰う", "もらう", "v5u"),
+        ("頂く", "いただく", "v5k"),
+        GrammaticalForm::Humble,
+    ),
+    SuppletiveVerb::new(
+        ("上げる", "あげる", "v1"),
+        ("差し上げる", "さしあげる", "v1"),
+        GrammaticalForm::Humble,
+    ),
+    SuppletiveVerb::new(
+        ("会う", "あう", "v5u"),
+        ("お目にかかる", "おめにかかる", "v5r"),
+        GrammaticalForm::Humble,
+    ),
+    SuppletiveVerb::new(
+        ("聞く", "きく", "v5k"),
+        ("伺う", "うかがう", "v5u"),
+        GrammaticalForm::Humble,
+    ),
+    SuppletiveVerb::new(
+        ("訪ねる", "たずねる", "v1"),
+        ("伺う", "うかがう", "v5u"),
+        GrammaticalForm::Humble,
+    ),
+    SuppletiveVerb::new(
+        ("寝る", "ねる", "v1"),
+        ("お休みになる", "おやすみになる", "v5r"),
+        GrammaticalForm::Honorific,
+    ),
+    SuppletiveVerb::new(
+        ("着る", "きる", "v1"),
+        ("お召しになる", "おめしになる", "v5r"),
+        GrammaticalForm::Honorific,
+    ),
+];
+
+fn keigo_conjugations(
+    verb_kanji_form: &str,
+    verb_kana_form: &str,
+    category: &str,
+) -> Result<Vec<ConjugatedWord>> {
+    let suppletive_verbs: Vec<&SuppletiveVerb> = SUPPLETIVE_VERBS
+        .iter()
+        .filter(|verb| {
+            // readings alone are ambiguous, 鋳る is read いる as well
+            let spelled_in_kana = verb_kanji_form == verb_kana_form;
+            (verb.plain == verb_kanji_form
+                || spelled_in_kana && verb.plain_reading == verb_kana_form)
+                && verb.plain_category == category
+        })
+        .collect();
+    let mut conjugations: Vec<ConjugatedWord> = Vec::new();
+    for verb in suppletive_verbs.iter() {
+        conjugations.extend(derived_paradigm(
+            verb.keigo,
+            verb.keigo_reading,
+            verb.keigo_category,
+            &[verb.form],
+        )?);
+    }
+    let has_suppletive = |form| suppletive_verbs.iter().any(|verb| verb.form == form);
+    match category {
+        "vs" => {
+            let noun_kanji_form = &verb_kanji_form[..verb_kanji_form.len() - 6];
+            let noun_kana_form = &verb_kana_form[..verb_kana_form.len() - 6];
+            conjugations.extend(derived_paradigm(
+                &[noun_kanji_form, "なさる"].concat(),
+                &[noun_kana_form, "なさる"].concat(),
+                "v5aru",
+                &[GrammaticalForm::Honorific],
+            )?);
+            conjugations.extend(derived_paradigm(
+                &[noun_kanji_form, "いたす"].concat(),
+                &[noun_kana_form, "いたす"].concat(),
+                "v5s",
+                &[GrammaticalForm::Humble],
+            )?);
+        }
+        "v1" | "v5b" | "v5g" | "v5k" | "v5m" | "v5n" | "v5r" | "v5s" | "v5t" | "v5u" => {
+            let stem_kanji_form = masu_stem(verb_kanji_form, category, false)?;
+            let stem_kana_form = masu_stem(verb_kana_form, category, true)?;
+            // one mora stems like 見 or 寝 only take suppletive forms
+            if stem_kana_form.chars().count() > 1 {
+                if !has_suppletive(GrammaticalForm::Honorific) {
+                    conjugations.extend(derived_paradigm(
+                        &["お", &stem_kanji_form, "になる"].concat(),
+                        &["お", &stem_kana_form, "になる"].concat(),
+                        "v5r",
+                        &[GrammaticalForm::Honorific],
+                    )?);
+                }
+                if !has_suppletive(GrammaticalForm::Humble) {
+                    conjugations.extend(derived_paradigm(
+                        &["お", &stem_kanji_form, "する"].concat(),
+                        &["お", &stem_kana_form, "する"].concat(),
+                        "vs-i",
+                        &[GrammaticalForm::Humble],
+                    )?);
+                }
+            }
+        }
+        _ => return Ok(conjugations),
+    }
+    let passive_category = match category {
+        "vs" => "vs-i",
+        _ => category,
+    };
+    conjugations.extend(derived_paradigm(
+        &passive(verb_kanji_form, passive_category, false)?,
+        &passive(verb_kana_form, passive_category, true)?,
+        "v1",
+        &[GrammaticalForm::Honorific, GrammaticalForm::Passive],
+    )?);
+    Ok(conjugations)
+}
+
+type ConjugationFn = fn(&str, &str, bool) -> Result<String>;
+
+fn auxiliary_conjugations(
+    verb_kanji_form: &str,
+    verb_kana_form: &str,
+    category: &str,
+) -> Result<Vec<ConjugatedWord>> {
+    use GrammaticalForm::*;
+    let auxiliaries: [(ConjugationFn, &str, &[GrammaticalForm]); 7] = [
+        (progressive, "v1", &[Progressive]),
+        (progressive_short, "v1", &[Progressive, Short]),
+        (completive, "v5u", &[Completive]),
+        (completive_short, "v5u", &[Completive, Short]),
+        (preparatory, "v5k", &[Preparatory]),
+        (preparatory_short, "v5k", &[Preparatory, Short]),
+        (attempt, "v1", &[Attempt]),
+    ];
+    let mut conjugations: Vec<ConjugatedWord> = Vec::new();
+    for (auxiliary, auxiliary_category, forms) in auxiliaries {
+        conjugations.extend(derived_paradigm(
+            &auxiliary(verb_kanji_form, category, false)?,
+            &auxiliary(verb_kana_form, category, true)?,
+            auxiliary_category,
+            forms,
+        )?);
     }
+    // たい conjugates like an i-adjective
+    let desire_forms: [(ConjugationFn, &[GrammaticalForm]); 4] = [
+        (desire_te, &[Desire, TeForm]),
+        (desire_adverbial, &[Desire, Adverbial]),
+        (desire_conditional, &[Desire, Conditional]),
+        (
+            desire_provisional_conditional,
+            &[Desire, ProvisionalConditional],
+        ),
+    ];
+    for (desire_form, forms) in desire_forms {
+        conjugations.push(ConjugatedWord::new(
+            desire_form(verb_kanji_form, category, false)?,
+            desire_form(verb_kana_form, category, true)?,
+            forms.to_vec(),
+        ));
+    }
+    Ok(conjugations)
+}
+
+fn derived_paradigm(
+    verb_kanji_form: &str,
+    verb_kana_form: &str,
+    category: &str,
+    derived_forms: &[GrammaticalForm],
+) -> Result<Vec<ConjugatedWord>> {
+    let paradigm_forms = [
+        GrammaticalForm::Negative,
+        GrammaticalForm::Past,
+        GrammaticalForm::Polite,
+        GrammaticalForm::TeForm,
+    ];
+    let dictionary_form = ConjugatedWord::new(
+        verb_kanji_form.to_string(),
+        verb_kana_form.to_string(),
+        Vec::new(),
+    );
+    Ok(std::iter::once(dictionary_form)
+        .chain(conjugate_verb(verb_kanji_form, verb_kana_form, category)?)
+        .filter(|conjugation| {
+            conjugation
+                .grammatical_forms
+                .iter()
+                .all(|form| paradigm_forms.contains(form))
+        })
+        .map(|conjugation| {
+            ConjugatedWord::new(
+                conjugation.kanji_form,
+                conjugation.kana_form,
+                derived_forms
+                    .iter()
+                    .chain(conjugation.grammatical_forms.iter())
+                    .copied()
+                    .collect(),
+            )
+        })
+        .collect())
+}
+
+fn is_verb_category(tag: &str) -> bool {
+    VERB_TAGS.contains(tag) || tag.starts_with("v2") || tag.starts_with("v4")
+}
+
+fn conjugate_verb(
+    verb_kanji_form: &str,
+    verb_kana_form: &str,
+    category: &str,
+) -> Result<Vec<ConjugatedWord>> {
+    Ok(vec![
+        // plain forms
+        ConjugatedWord::new(
+            masu_stem(verb_kanji_form, category, false)?,
+            masu_stem(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::MasuStem],
+        ),
+        ConjugatedWord::new(
+            causative(verb_kanji_form, category, false)?,
+            causative(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Causative],
+        ),
+        ConjugatedWord::new(
+            causative_passive(verb_kanji_form, category, false)?,
+            causative_passive(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Causative, GrammaticalForm::Passive],
+        ),
+        ConjugatedWord::new(
+            causative_short(verb_kanji_form, category, false)?,
+            causative_short(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Causative, GrammaticalForm::Short],
+        ),
+        ConjugatedWord::new(
+            conditional(verb_kanji_form, category, false)?,
+            conditional(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Conditional],
+        ),
+        ConjugatedWord::new(
+            desire(verb_kanji_form, category, false)?,
+            desire(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Desire],
+        ),
+        ConjugatedWord::new(
+            imperative(verb_kanji_form, category, false)?,
+            imperative(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Imperative],
+        ),
+        ConjugatedWord::new(
+            passive(verb_kanji_form, category, false)?,
+            passive(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Passive],
+        ),
+        ConjugatedWord::new(
+            potential(verb_kanji_form, category, false)?,
+            potential(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Potential],
+        ),
+        ConjugatedWord::new(
+            provisional_conditional(verb_kanji_form, category, false)?,
+            provisional_conditional(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::ProvisionalConditional],
+        ),
+        ConjugatedWord::new(
+            volitional(verb_kanji_form, category, false)?,
+            volitional(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Volitional],
+        ),
+        // Negative forms
+        ConjugatedWord::new(
+            negative(verb_kanji_form, category, false)?,
+            negative(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Negative],
+        ),
+        ConjugatedWord::new(
+            causative_negative(verb_kanji_form, category, false)?,
+            causative_negative(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Causative, GrammaticalForm::Negative],
+        ),
+        ConjugatedWord::new(
+            causative_passive_negative(verb_kanji_form, category, false)?,
+            causative_passive_negative(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Causative,
+                GrammaticalForm::Passive,
+                GrammaticalForm::Negative,
+            ],
+        ),
+        ConjugatedWord::new(
+            conditional_negative(verb_kanji_form, category, false)?,
+            conditional_negative(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Negative, GrammaticalForm::Conditional],
+        ),
+        ConjugatedWord::new(
+            desire_negative(verb_kanji_form, category, false)?,
+            desire_negative(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Desire, GrammaticalForm::Negative],
+        ),
+        ConjugatedWord::new(
+            imperative_negative(verb_kanji_form, category, false)?,
+            imperative_negative(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Imperative, GrammaticalForm::Negative],
+        ),
+        ConjugatedWord::new(
+            passive_negative(verb_kanji_form, category, false)?,
+            passive_negative(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Passive, GrammaticalForm::Negative],
+        ),
+        ConjugatedWord::new(
+            potential_negative(verb_kanji_form, category, false)?,
+            potential_negative(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Potential, GrammaticalForm::Negative],
+        ),
+        // Past forms
+        ConjugatedWord::new(
+            past(verb_kanji_form, category, false)?,
+            past(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Past],
+        ),
+        ConjugatedWord::new(
+            causative_past(verb_kanji_form, category, false)?,
+            causative_past(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Causative, GrammaticalForm::Past],
+        ),
+        ConjugatedWord::new(
+            causative_passive_past(verb_kanji_form, category, false)?,
+            causative_passive_past(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Causative,
+                GrammaticalForm::Passive,
+                GrammaticalForm::Past,
+            ],
+        ),
+        ConjugatedWord::new(
+            desire_past(verb_kanji_form, category, false)?,
+            desire_past(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Desire, GrammaticalForm::Past],
+        ),
+        ConjugatedWord::new(
+            passive_past(verb_kanji_form, category, false)?,
+            passive_past(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Passive, GrammaticalForm::Past],
+        ),
+        ConjugatedWord::new(
+            potential_past(verb_kanji_form, category, false)?,
+            potential_past(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Potential, GrammaticalForm::Past],
+        ),
+        // Past negative forms
+        ConjugatedWord::new(
+            past_negative(verb_kanji_form, category, false)?,
+            past_negative(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Negative, GrammaticalForm::Past],
+        ),
+        ConjugatedWord::new(
+            causative_past_negative(verb_kanji_form, category, false)?,
+            causative_past_negative(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Causative,
+                GrammaticalForm::Negative,
+                GrammaticalForm::Past,
+            ],
+        ),
+        ConjugatedWord::new(
+            causative_passive_past_negative(verb_kanji_form, category, false)?,
+            causative_passive_past_negative(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Causative,
+                GrammaticalForm::Passive,
+                GrammaticalForm::Negative,
+                GrammaticalForm::Past,
+            ],
+        ),
+        ConjugatedWord::new(
+            desire_past_negative(verb_kanji_form, category, false)?,
+            desire_past_negative(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Desire,
+                GrammaticalForm::Negative,
+                GrammaticalForm::Past,
+            ],
+        ),
+        ConjugatedWord::new(
+            passive_past_negative(verb_kanji_form, category, false)?,
+            passive_past_negative(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Passive,
+                GrammaticalForm::Negative,
+                GrammaticalForm::Past,
+            ],
+        ),
+        ConjugatedWord::new(
+            potential_past_negative(verb_kanji_form, category, false)?,
+            potential_past_negative(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Potential,
+                GrammaticalForm::Negative,
+                GrammaticalForm::Past,
+            ],
+        ),
+        // Te-forms
+        ConjugatedWord::new(
+            te_form(verb_kanji_form, category, false)?,
+            te_form(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::TeForm],
+        ),
+        ConjugatedWord::new(
+            causative_te(verb_kanji_form, category, false)?,
+            causative_te(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Causative, GrammaticalForm::TeForm],
+        ),
+        ConjugatedWord::new(
+            causative_passive_te(verb_kanji_form, category, false)?,
+            causative_passive_te(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Causative,
+                GrammaticalForm::Passive,
+                GrammaticalForm::TeForm,
+            ],
+        ),
+        ConjugatedWord::new(
+            passive_te(verb_kanji_form, category, false)?,
+            passive_te(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Passive, GrammaticalForm::TeForm],
+        ),
+        ConjugatedWord::new(
+            potential_te(verb_kanji_form, category, false)?,
+            potential_te(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Potential, GrammaticalForm::TeForm],
+        ),
+        // Polite forms
+        ConjugatedWord::new(
+            polite(verb_kanji_form, category, false)?,
+            polite(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Polite],
+        ),
+        ConjugatedWord::new(
+            causative_polite(verb_kanji_form, category, false)?,
+            causative_polite(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Causative, GrammaticalForm::Polite],
+        ),
+        ConjugatedWord::new(
+            causative_passive_polite(verb_kanji_form, category, false)?,
+            causative_passive_polite(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Causative,
+                GrammaticalForm::Passive,
+                GrammaticalForm::Polite,
+            ],
+        ),
+        ConjugatedWord::new(
+            passive_polite(verb_kanji_form, category, false)?,
+            passive_polite(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Passive, GrammaticalForm::Polite],
+        ),
+        ConjugatedWord::new(
+            potential_polite(verb_kanji_form, category, false)?,
+            potential_polite(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Potential, GrammaticalForm::Polite],
+        ),
+        ConjugatedWord::new(
+            volitional_polite(verb_kanji_form, category, false)?,
+            volitional_polite(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Polite, GrammaticalForm::Volitional],
+        ),
+        // Polite negative forms
+        ConjugatedWord::new(
+            polite_negative(verb_kanji_form, category, false)?,
+            polite_negative(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Polite, GrammaticalForm::Negative],
+        ),
+        ConjugatedWord::new(
+            causative_polite_negative(verb_kanji_form, category, false)?,
+            causative_polite_negative(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Causative,
+                GrammaticalForm::Polite,
+                GrammaticalForm::Negative,
+            ],
+        ),
+        ConjugatedWord::new(
+            causative_passive_polite_negative(verb_kanji_form, category, false)?,
+            causative_passive_polite_negative(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Causative,
+                GrammaticalForm::Passive,
+                GrammaticalForm::Polite,
+                GrammaticalForm::Negative,
+            ],
+        ),
+        ConjugatedWord::new(
+            passive_polite_negative(verb_kanji_form, category, false)?,
+            passive_polite_negative(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Passive,
+                GrammaticalForm::Polite,
+                GrammaticalForm::Negative,
+            ],
+        ),
+        ConjugatedWord::new(
+            potential_polite_negative(verb_kanji_form, category, false)?,
+            potential_polite_negative(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Potential,
+                GrammaticalForm::Polite,
+                GrammaticalForm::Negative,
+            ],
+        ),
+        // Polite past forms
+        ConjugatedWord::new(
+            polite_past(verb_kanji_form, category, false)?,
+            polite_past(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Polite, GrammaticalForm::Past],
+        ),
+        ConjugatedWord::new(
+            causative_polite_past(verb_kanji_form, category, false)?,
+            causative_polite_past(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Causative,
+                GrammaticalForm::Polite,
+                GrammaticalForm::Past,
+            ],
+        ),
+        ConjugatedWord::new(
+            causative_passive_polite_past(verb_kanji_form, category, false)?,
+            causative_passive_polite_past(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Causative,
+                GrammaticalForm::Passive,
+                GrammaticalForm::Polite,
+                GrammaticalForm::Past,
+            ],
+        ),
+        ConjugatedWord::new(
+            passive_polite_past(verb_kanji_form, category, false)?,
+            passive_polite_past(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Passive,
+                GrammaticalForm::Polite,
+                GrammaticalForm::Past,
+            ],
+        ),
+        ConjugatedWord::new(
+            potential_polite_past(verb_kanji_form, category, false)?,
+            potential_polite_past(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Potential,
+                GrammaticalForm::Polite,
+                GrammaticalForm::Past,
+            ],
+        ),
+        // Polite past negative forms
+        ConjugatedWord::new(
+            polite_past_negative(verb_kanji_form, category, false)?,
+            polite_past_negative(verb_kana_form, category, true)?,
+            vec![
+                GrammaticalForm::Polite,
+                GrammaticalForm::Negative,
+                GrammaticalForm::Past,
+            ],
+        ),
+        // Polite te-form
+        ConjugatedWord::new(
+            polite_te_form(verb_kanji_form, category, false)?,
+            polite_te_form(verb_kana_form, category, true)?,
+            vec![GrammaticalForm::Polite, GrammaticalForm::TeForm],
+        ),
+    ])
 }
 
-fn masu_stem(verb: &str, category: &str, reading: bool) -> String {
+struct ClassicalStems {
+    irrealis: String,
+    continuative: String,
+    attributive: String,
+    realis: String,
+    imperative: String,
+}
+
+fn conjugate_classical_verb(
+    verb_kanji_form: &str,
+    verb_kana_form: &str,
+    category: &str,
+) -> Result<Vec<ConjugatedWord>> {
+    let kanji_stems = classical_stems(verb_kanji_form, category)?;
+    let kana_stems = classical_stems(verb_kana_form, category)?;
+    let forms = |stems: &ClassicalStems| {
+        [
+            (stems.irrealis.clone() + "ず", GrammaticalForm::Negative),
+            (stems.continuative.clone(), GrammaticalForm::MasuStem),
+            (stems.attributive.clone(), GrammaticalForm::Attributive),
+            (
+                stems.realis.clone() + "ば",
+                GrammaticalForm::ProvisionalConditional,
+            ),
+            (stems.imperative.clone(), GrammaticalForm::Imperative),
+        ]
+    };
+    Ok(forms(&kanji_stems)
+        .into_iter()
+        .zip(forms(&kana_stems))
+        .map(|((kanji_form, form), (kana_form, _))| {
+            ConjugatedWord::new(kanji_form, kana_form, vec![form])
+        })
+        .collect())
+}
+
+fn classical_stems(verb: &str, category: &str) -> Result<ClassicalStems> {
+    let row = category.chars().nth(2).unwrap_or_default();
+    let (a, i, u, e) = match (category, row) {
+        ("vr", _) => ("ら", "り", "る", "れ"),
+        (_, 'a') => ("あ", "い", "う", "え"),
+        (_, 'b') => ("ば", "び", "ぶ", "べ"),
+        (_, 'd') => ("だ", "ぢ", "づ", "で"),
+        (_, 'g') => ("が", "ぎ", "ぐ", "げ"),
+        (_, 'h') => ("は", "ひ", "ふ", "へ"),
+        (_, 'k') => ("か", "き", "く", "け"),
+        (_, 'm') => ("ま", "み", "む", "め"),
+        (_, 'n') => ("な", "に", "ぬ", "ね"),
+        (_, 'r') => ("ら", "り", "る", "れ"),
+        (_, 's') => ("さ", "し", "す", "せ"),
+        (_, 't') => ("た", "ち", "つ", "て"),
+        (_, 'w') => ("わ", "ゐ", "う", "ゑ"),
+        (_, 'y') => ("や", "い", "ゆ", "え"),
+        (_, 'z') => ("ざ", "じ", "ず", "ぜ"),
+        _ => return Err(ConjugationError::new(verb, category).into()),
+    };
+    let last = match category {
+        "vr" => i,
+        _ => u,
+    };
+    let Some(prefix) = verb.strip_suffix(last) else {
+        return Err(ConjugationError::new(verb, category).into());
+    };
+    let stems = match category {
+        "vr" => ClassicalStems {
+            irrealis: [prefix, a].concat(),
+            continuative: verb.to_string(),
+            attributive: [prefix, u].concat(),
+            realis: [prefix, e].concat(),
+            imperative: [prefix, e].concat(),
+        },
+        _ if category.starts_with("v4") => ClassicalStems {
+            irrealis: [prefix, a].concat(),
+            continuative: [prefix, i].concat(),
+            attributive: verb.to_string(),
+            realis: [prefix, e].concat(),
+            imperative: [prefix, e].concat(),
+        },
+        // upper (-k) and lower (-s) bigrade verbs
+        _ => {
+            let stem = match category.ends_with("-k") {
+                true => [prefix, i].concat(),
+                false => [prefix, e].concat(),
+            };
+            ClassicalStems {
+                irrealis: stem.clone(),
+                continuative: stem.clone(),
+                attributive: [prefix, u, "る"].concat(),
+                realis: [prefix, u, "れ"].concat(),
+                imperative: stem + "よ",
+            }
+        }
+    };
+    Ok(stems)
+}
+
+fn masu_stem(verb: &str, category: &str, reading: bool) -> Result<String> {
     let (ending, suffix_len) = match category {
         "v1" | "v1-s" => ("", 1),
         "v5aru" | "v5u" | "v5u-s" => ("い", 1),
@@ -410,13 +971,13 @@ fn masu_stem(verb: &str, category: &str, reading: bool) -> String {
         },
         "vs-i" | "vs-s" => ("し", 2),
         "vz" => ("じ", 2),
-        _ => panic!("format '{}' unknown", category),
+        _ => return Err(ConjugationError::new(verb, category).into()),
     };
     let prefix_len = verb.len() - suffix_len * 3;
-    [&verb[..prefix_len], ending].concat()
+    Ok([&verb[..prefix_len], ending].concat())
 }
 
-fn causative(verb: &str, category: &str, reading: bool) -> String {
+fn causative(verb: &str, category: &str, reading: bool) -> Result<String> {
     let (ending, suffix_len) = match category {
         "v1" | "v1-s" | "v5s" => ("させる", 1),
         "v5aru" | "v5r" | "v5r-i" => ("らせる", 1),
@@ -433,31 +994,31 @@ fn causative(verb: &str, category: &str, reading: bool) -> String {
         },
         "vs-i" | "vs-s" => ("させる", 2),
         "vz" => ("じさせる", 2),
-        _ => panic!("format '{}' unknown", category),
+        _ => return Err(ConjugationError::new(verb, category).into()),
     };
     let prefix_len = verb.len() - suffix_len * 3;
-    [&verb[..prefix_len], ending].concat()
+    Ok([&verb[..prefix_len], ending].concat())
 }
 
-fn causative_passive(verb: &str, category: &str, reading: bool) -> String {
-    let causative = causative(verb, category, reading);
-    [&causative[..causative.len() - 3], "られる"].concat()
+fn causative_passive(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let causative = causative(verb, category, reading)?;
+    Ok([&causative[..causative.len() - 3], "られる"].concat())
 }
 
-fn causative_short(verb: &str, category: &str, reading: bool) -> String {
-    let causative = causative(verb, category, reading);
-    [&causative[..causative.len() - 6], "す"].concat()
+fn causative_short(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let causative = causative(verb, category, reading)?;
+    Ok([&causative[..causative.len() - 6], "す"].concat())
 }
 
-fn conditional(verb: &str, category: &str, reading: bool) -> String {
-    past(verb, category, reading) + "ら"
+fn conditional(verb: &str, category: &str, reading: bool) -> Result<String> {
+    Ok(past(verb, category, reading)? + "ら")
 }
 
-fn desire(verb: &str, category: &str, reading: bool) -> String {
-    masu_stem(verb, category, reading) + "たい"
+fn desire(verb: &str, category: &str, reading: bool) -> Result<String> {
+    Ok(masu_stem(verb, category, reading)? + "たい")
 }
 
-fn imperative(verb: &str, category: &str, reading: bool) -> String {
+fn imperative(verb: &str, category: &str, reading: bool) -> Result<String> {
     let (ending, suffix_len) = match category {
         "v1" => ("ろ", 1),
         "v1-s" => ("", 0),
@@ -477,13 +1038,13 @@ fn imperative(verb: &str, category: &str, reading: bool) -> String {
         },
         "vs-i" | "vs-s" => ("しろ", 2),
         "vz" => ("じろ", 2),
-        _ => panic!("format '{}' unknown", category),
+        _ => return Err(ConjugationError::new(verb, category).into()),
     };
     let prefix_len = verb.len() - suffix_len * 3;
-    [&verb[..prefix_len], ending].concat()
+    Ok([&verb[..prefix_len], ending].concat())
 }
 
-fn passive(verb: &str, category: &str, reading: bool) -> String {
+fn passive(verb: &str, category: &str, reading: bool) -> Result<String> {
     let (ending, suffix_len) = match category {
         "v1" | "v1-s" | "v5aru" | "v5r" | "v5r-i" => ("られる", 1),
         "v5b" => ("ばれる", 1),
@@ -500,13 +1061,13 @@ fn passive(verb: &str, category: &str, reading: bool) -> String {
         },
         "vs-i" | "vs-s" => ("される", 2),
         "vz" => ("じられる", 2),
-        _ => panic!("format '{}' unknown", category),
+        _ => return Err(ConjugationError::new(verb, category).into()),
     };
     let prefix_len = verb.len() - suffix_len * 3;
-    [&verb[..prefix_len], ending].concat()
+    Ok([&verb[..prefix_len], ending].concat())
 }
 
-fn potential(verb: &str, category: &str, reading: bool) -> String {
+fn potential(verb: &str, category: &str, reading: bool) -> Result<String> {
     let (ending, suffix_len) = match category {
         "v1" | "v1-s" => ("られる", 1),
         "v5aru" => ("り得る", 1),
@@ -526,13 +1087,13 @@ fn potential(verb: &str, category: &str, reading: bool) -> String {
         },
         "vs-i" | "vs-s" => ("できる", 2),
         "vz" => ("じられる", 2),
-        _ => panic!("format '{}' unknown", category),
+        _ => return Err(ConjugationError::new(verb, category).into()),
     };
     let prefix_len = verb.len() - suffix_len * 3;
-    [&verb[..prefix_len], ending].concat()
+    Ok([&verb[..prefix_len], ending].concat())
 }
 
-fn provisional_conditional(verb: &str, category: &str, _reading: bool) -> String {
+fn provisional_conditional(verb: &str, category: &str, _reading: bool) -> Result<String> {
     let (ending, suffix_len) = match category {
         "v1" | "v1-s" | "v5aru" | "v5r" | "v5r-i" | "vs-i" | "vs-s" | "vz" | "vk" => ("れば", 1),
         "v5b" => ("べば", 1),
@@ -543,13 +1104,13 @@ fn provisional_conditional(verb: &str, category: &str, _reading: bool) -> String
         "v5s" => ("せば", 1),
         "v5t" => ("てば", 1),
         "v5u" | "v5u-s" => ("えば", 1),
-        _ => panic!("format '{}' unknown", category),
+        _ => return Err(ConjugationError::new(verb, category).into()),
     };
     let prefix_len = verb.len() - suffix_len * 3;
-    [&verb[..prefix_len], ending].concat()
+    Ok([&verb[..prefix_len], ending].concat())
 }
 
-fn volitional(verb: &str, category: &str, reading: bool) -> String {
+fn volitional(verb: &str, category: &str, reading: bool) -> Result<String> {
     let (ending, suffix_len) = match category {
         "v1" | "v1-s" => ("よう", 1),
         "v5aru" | "v5r" | "v5r-i" => ("ろう", 1),
@@ -567,13 +1128,13 @@ fn volitional(verb: &str, category: &str, reading: bool) -> String {
         },
         "vs-i" | "vs-s" => ("しよう", 2),
         "vz" => ("じよう", 2),
-        _ => panic!("format '{}' unknown", category),
+        _ => return Err(ConjugationError::new(verb, category).into()),
     };
     let prefix_len = verb.len() - suffix_len * 3;
-    [&verb[..prefix_len], ending].concat()
+    Ok([&verb[..prefix_len], ending].concat())
 }
 
-fn negative(verb: &str, category: &str, reading: bool) -> String {
+fn negative(verb: &str, category: &str, reading: bool) -> Result<String> {
     let (ending, suffix_len) = match category {
         "v1" | "v1-s" => ("ない", 1),
         "v5b" => ("ばない", 1),
@@ -592,42 +1153,42 @@ fn negative(verb: &str, category: &str, reading: bool) -> String {
         },
         "vs-i" | "vs-s" => ("しない", 2),
         "vz" => ("じない", 2),
-        _ => panic!("format '{}' unknown", category),
+        _ => return Err(ConjugationError::new(verb, category).into()),
     };
     let prefix_len = verb.len() - suffix_len * 3;
-    [&verb[..prefix_len], ending].concat()
+    Ok([&verb[..prefix_len], ending].concat())
 }
 
-fn causative_negative(verb: &str, category: &str, reading: bool) -> String {
-    negative(&causative(verb, category, reading), "v1", reading)
+fn causative_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    negative(&causative(verb, category, reading)?, "v1", reading)
 }
 
-fn causative_passive_negative(verb: &str, category: &str, reading: bool) -> String {
-    negative(&causative(verb, category, reading), "v1", reading)
+fn causative_passive_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    negative(&causative_passive(verb, category, reading)?, "v1", reading)
 }
 
-fn conditional_negative(verb: &str, category: &str, reading: bool) -> String {
-    past_negative(verb, category, reading) + "ら"
+fn conditional_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    Ok(past_negative(verb, category, reading)? + "ら")
 }
 
-fn desire_negative(verb: &str, category: &str, reading: bool) -> String {
-    let tai = desire(verb, category, reading);
-    [&tai[..tai.len() - 3], "くない"].concat()
+fn desire_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let tai = desire(verb, category, reading)?;
+    Ok([&tai[..tai.len() - 3], "くない"].concat())
 }
 
-fn imperative_negative(verb: &str, category: &str, reading: bool) -> String {
-    imperative(verb, category, reading) + "な"
+fn imperative_negative(verb: &str, _category: &str, _reading: bool) -> Result<String> {
+    Ok(verb.to_string() + "な")
 }
 
-fn passive_negative(verb: &str, category: &str, reading: bool) -> String {
-    negative(&passive(verb, category, reading), "v1", reading)
+fn passive_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    negative(&passive(verb, category, reading)?, "v1", reading)
 }
 
-fn potential_negative(verb: &str, category: &str, reading: bool) -> String {
-    negative(&potential(verb, category, reading), "v1", reading)
+fn potential_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    negative(&potential(verb, category, reading)?, "v1", reading)
 }
 
-fn past(verb: &str, category: &str, reading: bool) -> String {
+fn past(verb: &str, category: &str, reading: bool) -> Result<String> {
     let (ending, suffix_len) = match category {
         "v1" | "v1-s" => ("た", 1),
         "v5aru" | "v5k-s" | "v5r" | "v5r-i" | "v5t" | "v5u" => ("った", 1),
@@ -642,60 +1203,60 @@ fn past(verb: &str, category: &str, reading: bool) -> String {
         },
         "vs-i" | "vs-s" => ("した", 2),
         "vz" => ("じた", 2),
-        _ => panic!("format '{}' unknown", category),
+        _ => return Err(ConjugationError::new(verb, category).into()),
     };
     let prefix_len = verb.len() - suffix_len * 3;
-    [&verb[..prefix_len], ending].concat()
+    Ok([&verb[..prefix_len], ending].concat())
 }
 
-fn causative_past(verb: &str, category: &str, reading: bool) -> String {
-    past(&causative(verb, category, reading), "v1", reading)
+fn causative_past(verb: &str, category: &str, reading: bool) -> Result<String> {
+    past(&causative(verb, category, reading)?, "v1", reading)
 }
 
-fn causative_passive_past(verb: &str, category: &str, reading: bool) -> String {
-    past(&causative_passive(verb, category, reading), "v1", reading)
+fn causative_passive_past(verb: &str, category: &str, reading: bool) -> Result<String> {
+    past(&causative_passive(verb, category, reading)?, "v1", reading)
 }
 
-fn desire_past(verb: &str, category: &str, reading: bool) -> String {
-    let tai = desire(verb, category, reading);
-    [&tai[..tai.len() - 3], "かった"].concat()
+fn desire_past(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let tai = desire(verb, category, reading)?;
+    Ok([&tai[..tai.len() - 3], "かった"].concat())
 }
 
-fn passive_past(verb: &str, category: &str, reading: bool) -> String {
-    past(&passive(verb, category, reading), "v1", reading)
+fn passive_past(verb: &str, category: &str, reading: bool) -> Result<String> {
+    past(&passive(verb, category, reading)?, "v1", reading)
 }
 
-fn potential_past(verb: &str, category: &str, reading: bool) -> String {
-    past(&potential(verb, category, reading), "v1", reading)
+fn potential_past(verb: &str, category: &str, reading: bool) -> Result<String> {
+    past(&potential(verb, category, reading)?, "v1", reading)
 }
 
-fn past_negative(verb: &str, category: &str, reading: bool) -> String {
-    let negative = negative(verb, category, reading);
-    [&negative[..negative.len() - 3], "かった"].concat()
+fn past_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let negative = negative(verb, category, reading)?;
+    Ok([&negative[..negative.len() - 3], "かった"].concat())
 }
 
-fn causative_past_negative(verb: &str, category: &str, reading: bool) -> String {
-    past_negative(&causative(verb, category, reading), "v1", reading)
+fn causative_past_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    past_negative(&causative(verb, category, reading)?, "v1", reading)
 }
 
-fn causative_passive_past_negative(verb: &str, category: &str, reading: bool) -> String {
-    past_negative(&causative_passive(verb, category, reading), "v1", reading)
+fn causative_passive_past_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    past_negative(&causative_passive(verb, category, reading)?, "v1", reading)
 }
 
-fn desire_past_negative(verb: &str, category: &str, reading: bool) -> String {
-    let tai = desire(verb, category, reading);
-    [&tai[..tai.len() - 3], "くなかった"].concat()
+fn desire_past_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let tai = desire(verb, category, reading)?;
+    Ok([&tai[..tai.len() - 3], "くなかった"].concat())
 }
 
-fn passive_past_negative(verb: &str, category: &str, reading: bool) -> String {
-    past_negative(&passive(verb, category, reading), "v1", reading)
+fn passive_past_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    past_negative(&passive(verb, category, reading)?, "v1", reading)
 }
 
-fn potential_past_negative(verb: &str, category: &str, reading: bool) -> String {
-    past_negative(&potential(verb, category, reading), "v1", reading)
+fn potential_past_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    past_negative(&potential(verb, category, reading)?, "v1", reading)
 }
 
-fn te_form(verb: &str, category: &str, reading: bool) -> String {
+fn te_form(verb: &str, category: &str, reading: bool) -> Result<String> {
     let (ending, suffix_len) = match category {
         "v1" | "v1-s" => ("て", 1),
         "v5aru" | "v5k-s" | "v5r" | "v5r-i" | "v5t" | "v5u" => ("って", 1),
@@ -710,106 +1271,168 @@ fn te_form(verb: &str, category: &str, reading: bool) -> String {
         },
         "vs-i" | "vs-s" => ("して", 2),
         "vz" => ("じて", 2),
-        _ => panic!("format '{}' unknown", category),
+        _ => return Err(ConjugationError::new(verb, category).into()),
     };
     let prefix_len = verb.len() - suffix_len * 3;
-    [&verb[..prefix_len], ending].concat()
+    Ok([&verb[..prefix_len], ending].concat())
+}
+
+fn causative_te(verb: &str, category: &str, reading: bool) -> Result<String> {
+    te_form(&causative(verb, category, reading)?, "v1", reading)
 }
 
-fn causative_te(verb: &str, category: &str, reading: bool) -> String {
-    te_form(&causative(verb, category, reading), "v1", reading)
+fn causative_passive_te(verb: &str, category: &str, reading: bool) -> Result<String> {
+    te_form(&causative_passive(verb, category, reading)?, "v1", reading)
 }
 
-fn causative_passive_te(verb: &str, category: &str, reading: bool) -> String {
-    te_form(&causative_passive(verb, category, reading), "v1", reading)
+fn passive_te(verb: &str, category: &str, reading: bool) -> Result<String> {
+    te_form(&passive(verb, category, reading)?, "v1", reading)
 }
 
-fn passive_te(verb: &str, category: &str, reading: bool) -> String {
-    te_form(&passive(verb, category, reading), "v1", reading)
+fn potential_te(verb: &str, category: &str, reading: bool) -> Result<String> {
+    te_form(&potential(verb, category, reading)?, "v1", reading)
 }
 
-fn potential_te(verb: &str, category: &str, reading: bool) -> String {
-    te_form(&potential(verb, category, reading), "v1", reading)
+fn polite(verb: &str, category: &str, reading: bool) -> Result<String> {
+    Ok(masu_stem(verb, category, reading)? + "ます")
 }
 
-fn polite(verb: &str, category: &str, reading: bool) -> String {
-    masu_stem(verb, category, reading) + "ます"
+fn causative_polite(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let causative = causative(verb, category, reading)?;
+    Ok([&causative[..causative.len() - 3], "ます"].concat())
 }
 
-fn causative_polite(verb: &str, category: &str, reading: bool) -> String {
-    let causative = causative(verb, category, reading);
-    [&causative[..causative.len() - 3], "ます"].concat()
+fn causative_passive_polite(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let causative_passive = causative_passive(verb, category, reading)?;
+    Ok([&causative_passive[..causative_passive.len() - 3], "ます"].concat())
 }
 
-fn causative_passive_polite(verb: &str, category: &str, reading: bool) -> String {
-    let causative_passive = causative_passive(verb, category, reading);
-    [&causative_passive[..causative_passive.len() - 3], "ます"].concat()
+fn passive_polite(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let passive = passive(verb, category, reading)?;
+    Ok([&passive[..passive.len() - 3], "ます"].concat())
 }
 
-fn passive_polite(verb: &str, category: &str, reading: bool) -> String {
-    let passive = passive(verb, category, reading);
-    [&passive[..passive.len() - 3], "ます"].concat()
+fn potential_polite(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let potential = potential(verb, category, reading)?;
+    Ok([&potential[..potential.len() - 3], "ます"].concat())
 }
 
-fn potential_polite(verb: &str, category: &str, reading: bool) -> String {
-    let potential = potential(verb, category, reading);
-    [&potential[..potential.len() - 3], "ます"].concat()
+fn volitional_polite(verb: &str, category: &str, reading: bool) -> Result<String> {
+    Ok(masu_stem(verb, category, reading)? + "ましょう")
 }
 
-fn volitional_polite(verb: &str, category: &str, reading: bool) -> String {
-    masu_stem(verb, category, reading) + "ましょう"
+fn polite_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    Ok(masu_stem(verb, category, reading)? + "ません")
 }
 
-fn polite_negative(verb: &str, category: &str, reading: bool) -> String {
-    masu_stem(verb, category, reading) + "ません"
+fn causative_polite_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let causative = causative(verb, category, reading)?;
+    Ok([&causative[..causative.len() - 3], "ません"].concat())
 }
 
-fn causative_polite_negative(verb: &str, category: &str, reading: bool) -> String {
-    let causative = causative(verb, category, reading);
-    [&causative[..causative.len() - 3], "ません"].concat()
+fn causative_passive_polite_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let causative_passive = causative_passive(verb, category, reading)?;
+    Ok([&causative_passive[..causative_passive.len() - 3], "ません"].concat())
 }
 
-fn causative_passive_polite_negative(verb: &str, category: &str, reading: bool) -> String {
-    let causative_passive = causative_passive(verb, category, reading);
-    [&causative_passive[..causative_passive.len() - 3], "ません"].concat()
+fn passive_polite_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let passive = passive(verb, category, reading)?;
+    Ok([&passive[..passive.len() - 3], "ません"].concat())
 }
 
-fn passive_polite_negative(verb: &str, category: &str, reading: bool) -> String {
-    let passive = passive(verb, category, reading);
-    [&passive[..passive.len() - 3], "ません"].concat()
+fn potential_polite_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let potential = potential(verb, category, reading)?;
+    Ok([&potential[..potential.len() - 3], "ません"].concat())
 }
 
-fn potential_polite_negative(verb: &str, category: &str, reading: bool) -> String {
-    let potential = potential(verb, category, reading);
-    [&potential[..potential.len() - 3], "ません"].concat()
+fn polite_past(verb: &str, category: &str, reading: bool) -> Result<String> {
+    Ok(masu_stem(verb, category, reading)? + "ました")
 }
 
-fn polite_past(verb: &str, category: &str, reading: bool) -> String {
-    masu_stem(verb, category, reading) + "ました"
+fn causative_polite_past(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let causative = causative(verb, category, reading)?;
+    Ok([&causative[..causative.len() - 3], "ました"].concat())
 }
 
-fn causative_polite_past(verb: &str, category: &str, reading: bool) -> String {
-    let causative = causative(verb, category, reading);
-    [&causative[..causative.len() - 3], "ました"].concat()
+fn causative_passive_polite_past(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let causative_passive = causative_passive(verb, category, reading)?;
+    Ok([&causative_passive[..causative_passive.len() - 3], "ました"].concat())
 }
 
-fn causative_passive_polite_past(verb: &str, category: &str, reading: bool) -> String {
-    let causative_passive = causative_passive(verb, category, reading);
-    [&causative_passive[..causative_passive.len() - 3], "ました"].concat()
+fn passive_polite_past(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let passive = passive(verb, category, reading)?;
+    Ok([&passive[..passive.len() - 3], "ました"].concat())
 }
 
-fn passive_polite_past(verb: &str, category: &str, reading: bool) -> String {
-    let passive = passive(verb, category, reading);
-    [&passive[..passive.len() - 3], "ました"].concat()
+fn potential_polite_past(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let potential = potential(verb, category, reading)?;
+    Ok([&potential[..potential.len() - 3], "ました"].concat())
 }
 
-fn potential_polite_past(verb: &str, category: &str, reading: bool) -> String {
-    let potential = potential(verb, category, reading);
-    [&potential[..potential.len() - 3], "ました"].concat()
+fn polite_past_negative(verb: &str, category: &str, reading: bool) -> Result<String> {
+    Ok(polite_negative(verb, category, reading)? + "でした")
 }
 
-fn polite_te_form(verb: &str, category: &str, reading: bool) -> String {
-    masu_stem(verb, category, reading) + "まして"
+fn polite_te_form(verb: &str, category: &str, reading: bool) -> Result<String> {
+    Ok(masu_stem(verb, category, reading)? + "まして")
+}
+
+fn desire_te(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let tai = desire(verb, category, reading)?;
+    Ok([&tai[..tai.len() - 3], "くて"].concat())
+}
+
+fn desire_adverbial(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let tai = desire(verb, category, reading)?;
+    Ok([&tai[..tai.len() - 3], "く"].concat())
+}
+
+fn desire_conditional(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let tai = desire(verb, category, reading)?;
+    Ok([&tai[..tai.len() - 3], "かったら"].concat())
+}
+
+fn desire_provisional_conditional(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let tai = desire(verb, category, reading)?;
+    Ok([&tai[..tai.len() - 3], "ければ"].concat())
+}
+
+fn progressive(verb: &str, category: &str, reading: bool) -> Result<String> {
+    Ok(te_form(verb, category, reading)? + "いる")
+}
+
+fn progressive_short(verb: &str, category: &str, reading: bool) -> Result<String> {
+    Ok(te_form(verb, category, reading)? + "る")
+}
+
+fn completive(verb: &str, category: &str, reading: bool) -> Result<String> {
+    Ok(te_form(verb, category, reading)? + "しまう")
+}
+
+fn completive_short(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let te = te_form(verb, category, reading)?;
+    let ending = match te.ends_with('で') {
+        true => "じゃう",
+        false => "ちゃう",
+    };
+    Ok([&te[..te.len() - 3], ending].concat())
+}
+
+fn preparatory(verb: &str, category: &str, reading: bool) -> Result<String> {
+    Ok(te_form(verb, category, reading)? + "おく")
+}
+
+fn preparatory_short(verb: &str, category: &str, reading: bool) -> Result<String> {
+    let te = te_form(verb, category, reading)?;
+    let ending = match te.ends_with('で') {
+        true => "どく",
+        false => "とく",
+    };
+    Ok([&te[..te.len() - 3], ending].concat())
+}
+
+fn attempt(verb: &str, category: &str, reading: bool) -> Result<String> {
+    Ok(te_form(verb, category, reading)? + "みる")
 }
 
 lazy_static! {
@@ -830,6 +1453,10 @@ lazy_static! {
         String::from("v5t"),
         String::from("v5u"),
         String::from("v5u-s"),
+        String::from("vn"),
+        String::from("vr"),
+        String::from("vs"),
+        String::from("vs-c"),
         String::from("vs-i"),
         String::from("vs-s"),
         String::from("vz"),
@@ -837,3 +1464,223 @@ lazy_static! {
     .into_iter()
     .collect();
 }
+
+#[derive(Debug)]
+pub struct ConjugationError {
+    verb: String,
+    category: String,
+}
+
+impl ConjugationError {
+    fn new(verb: &str, category: &str) -> ConjugationError {
+        ConjugationError {
+            verb: verb.to_string(),
+            category: category.to_string(),
+        }
+    }
+}
+
+impl Error for ConjugationError {}
+
+impl fmt::Display for ConjugationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Cannot conjugate '{}' as verb of category '{}'",
+            self.verb, self.category
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conjugate(verb: &str, reading: &str, tag: &str) -> Vec<ConjugatedWord> {
+        let tag = String::from(tag);
+        generate_all_verb_conjugations(verb, reading, vec![&tag])
+            .unwrap()
+            .unwrap()
+    }
+
+    fn form(conjugations: &[ConjugatedWord], forms: &[GrammaticalForm]) -> (String, String) {
+        conjugations
+            .iter()
+            .find(|conjugation| conjugation.grammatical_forms == forms)
+            .map(|conjugation| {
+                (
+                    conjugation.kanji_form.to_owned(),
+                    conjugation.kana_form.to_owned(),
+                )
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_suru_noun() {
+        use GrammaticalForm::*;
+        let conjugations = conjugate("勉強", "べんきょう", "vs");
+        assert_eq!(
+            form(&conjugations, &[]),
+            ("勉強する".to_string(), "べんきょうする".to_string())
+        );
+        assert_eq!(
+            form(&conjugations, &[Negative, Past]),
+            (
+                "勉強しなかった".to_string(),
+                "べんきょうしなかった".to_string()
+            )
+        );
+        assert_eq!(
+            form(&conjugations, &[Potential]),
+            ("勉強できる".to_string(), "べんきょうできる".to_string())
+        );
+    }
+
+    #[test]
+    fn test_irregular_godan() {
+        use GrammaticalForm::*;
+        let iku = conjugate("行く", "いく", "v5k-s");
+        assert_eq!(
+            form(&iku, &[TeForm]),
+            ("行って".to_string(), "いって".to_string())
+        );
+        assert_eq!(
+            form(&iku, &[Past]),
+            ("行った".to_string(), "いった".to_string())
+        );
+        let aru = conjugate("有る", "ある", "v5r-i");
+        assert_eq!(form(&aru, &[Negative]).1, "ない");
+        assert_eq!(form(&aru, &[Negative, Past]).1, "なかった");
+        let aisu = conjugate("愛す", "あいす", "vs-c");
+        assert_eq!(
+            form(&aisu, &[Negative]),
+            ("愛さない".to_string(), "あいさない".to_string())
+        );
+    }
+
+    #[test]
+    fn test_several_verb_classes() {
+        let (v4k, v5k) = (String::from("v4k"), String::from("v5k"));
+        for tags in [vec![&v4k, &v5k], vec![&v5k, &v4k]] {
+            let namameku = generate_all_verb_conjugations("艶めく", "なまめく", tags)
+                .unwrap()
+                .unwrap();
+            assert_eq!(form(&namameku, &[GrammaticalForm::Polite]).0, "艶めきます");
+        }
+    }
+
+    #[test]
+    fn test_classical_verbs() {
+        use GrammaticalForm::*;
+        let ukuru = conjugate("受く", "うく", "v2k-s");
+        assert_eq!(
+            form(&ukuru, &[Negative]),
+            ("受けず".to_string(), "うけず".to_string())
+        );
+        assert_eq!(
+            form(&ukuru, &[Attributive]),
+            ("受くる".to_string(), "うくる".to_string())
+        );
+        assert_eq!(
+            form(&ukuru, &[Imperative]),
+            ("受けよ".to_string(), "うけよ".to_string())
+        );
+        let oku = conjugate("起く", "おく", "v2k-k");
+        assert_eq!(
+            form(&oku, &[MasuStem]),
+            ("起き".to_string(), "おき".to_string())
+        );
+        let kaku = conjugate("書く", "かく", "v4k");
+        assert_eq!(
+            form(&kaku, &[Negative]),
+            ("書かず".to_string(), "かかず".to_string())
+        );
+        assert_eq!(
+            form(&kaku, &[ProvisionalConditional]),
+            ("書けば".to_string(), "かけば".to_string())
+        );
+        let ari = conjugate("有り", "あり", "vr");
+        assert_eq!(
+            form(&ari, &[Negative]),
+            ("有らず".to_string(), "あらず".to_string())
+        );
+        assert_eq!(
+            form(&ari, &[Attributive]),
+            ("有る".to_string(), "ある".to_string())
+        );
+    }
+
+    #[test]
+    fn test_keigo() {
+        use GrammaticalForm::*;
+        let yomu = conjugate("読む", "よむ", "v5m");
+        assert_eq!(
+            form(&yomu, &[Honorific]),
+            ("お読みになる".to_string(), "およみになる".to_string())
+        );
+        assert_eq!(
+            form(&yomu, &[Humble, Polite]),
+            ("お読みします".to_string(), "およみします".to_string())
+        );
+        assert_eq!(
+            form(&yomu, &[Honorific, Passive]),
+            ("読まれる".to_string(), "よまれる".to_string())
+        );
+        let taberu = conjugate("食べる", "たべる", "v1");
+        assert_eq!(
+            form(&taberu, &[Honorific, Polite, Past]),
+            (
+                "召し上がりました".to_string(),
+                "めしあがりました".to_string()
+            )
+        );
+        assert_eq!(form(&taberu, &[Humble]).0, "頂く");
+        let iku = conjugate("行く", "いく", "v5k-s");
+        assert_eq!(form(&iku, &[Honorific, Polite]).1, "いらっしゃいます");
+        assert!(!conjugate("鋳る", "いる", "v1")
+            .iter()
+            .any(|conjugation| conjugation.kana_form == "いらっしゃる"));
+        assert!(!iku
+            .iter()
+            .any(|conjugation| conjugation.kana_form == "おいきになる"));
+        let benkyou = conjugate("勉強", "べんきょう", "vs");
+        assert_eq!(
+            form(&benkyou, &[Humble, Polite]),
+            (
+                "勉強いたします".to_string(),
+                "べんきょういたします".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_auxiliary_forms() {
+        use GrammaticalForm::*;
+        let taberu = conjugate("食べる", "たべる", "v1");
+        assert_eq!(form(&taberu, &[Progressive]).0, "食べている");
+        assert_eq!(form(&taberu, &[Progressive, Short, Past]).0, "食べてた");
+        assert_eq!(form(&taberu, &[Completive, Short, Past]).0, "食べちゃった");
+        assert_eq!(form(&taberu, &[Preparatory, Past]).0, "食べておいた");
+        assert_eq!(form(&taberu, &[Attempt, Polite]).0, "食べてみます");
+        assert_eq!(form(&taberu, &[Desire, TeForm]).0, "食べたくて");
+        let yomu = conjugate("読む", "よむ", "v5m");
+        assert_eq!(
+            form(&yomu, &[Completive, Short]),
+            ("読んじゃう".to_string(), "よんじゃう".to_string())
+        );
+        assert_eq!(form(&yomu, &[Preparatory, Short]).1, "よんどく");
+        let benkyou = conjugate("勉強", "べんきょう", "vs");
+        assert_eq!(form(&benkyou, &[Progressive]).0, "勉強している");
+    }
+
+    #[test]
+    fn test_unconjugatable_verb() {
+        let tag = String::from("v2a-s");
+        assert!(generate_all_verb_conjugations("得", "う", vec![&tag]).is_err());
+        let tag = String::from("n");
+        assert!(generate_all_verb_conjugations("犬", "いぬ", vec![&tag])
+            .unwrap()
+            .is_none());
+    }
+}
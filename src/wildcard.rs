@@ -0,0 +1,178 @@
+use itertools::Itertools;
+use regex::Regex;
+
+use crate::{
+    kana_utils::{katakana_to_hiragana, romaji_to_katakana},
+    DictionaryEntry,
+};
+
+const WILDCARDS: [char; 4] = ['*', '?', '＊', '？'];
+
+/// A pattern like `食べ*`, `*的` or `?き`, where `*` matches any number of
+/// characters and `?` exactly one.
+#[derive(Debug)]
+pub struct WildcardPattern {
+    prefix: String,
+    suffix: String,
+    regex: Regex,
+}
+
+impl WildcardPattern {
+    /// `None` if the query has no wildcards.
+    pub fn parse(query: &str) -> Option<WildcardPattern> {
+        let start = query.find(WILDCARDS)?;
+        let end = query.rfind(WILDCARDS)?;
+        let wildcard_len = query[end..].chars().next()?.len_utf8();
+        let regex = query
+            .chars()
+            .map(|c| match c {
+                '*' | '＊' => String::from(".*"),
+                '?' | '？' => String::from("."),
+                c => regex::escape(&c.to_string()),
+            })
+            .join("");
+        Some(WildcardPattern {
+            prefix: query[..start].to_string(),
+            suffix: query[end + wildcard_len..].to_string(),
+            regex: Regex::new(&format!("^{}$", regex)).ok()?,
+        })
+    }
+
+    /// The pattern itself, and in hiragana and katakana if it is romaji.
+    pub fn parse_with_kana(query: &str) -> Vec<WildcardPattern> {
+        let converted = query
+            .split_inclusive(WILDCARDS)
+            .map(|part| {
+                let literal = part.trim_end_matches(WILDCARDS);
+                let wildcard = &part[literal.len()..];
+                let katakana = match literal {
+                    "" => String::new(),
+                    literal => romaji_to_katakana(literal).ok()?,
+                };
+                let hiragana = katakana_to_hiragana(&katakana);
+                Some((hiragana + wildcard, katakana + wildcard))
+            })
+            .collect::<Option<Vec<(String, String)>>>()
+            .filter(|_| query.chars().any(|c| c.is_ascii_alphabetic()));
+        let queries = match converted {
+            Some(parts) => {
+                let (hiragana, katakana): (Vec<String>, Vec<String>) = parts.into_iter().unzip();
+                vec![query.to_string(), hiragana.concat(), katakana.concat()]
+            }
+            None => vec![query.to_string()],
+        };
+        queries
+            .iter()
+            .filter_map(|query| WildcardPattern::parse(query))
+            .collect()
+    }
+
+    pub fn is_match(&self, key: &str) -> bool {
+        self.regex.is_match(key)
+    }
+}
+
+/// Headwords and readings sorted by themselves and by their reversed
+/// characters, so prefixes and suffixes are a range of either.
+pub struct WildcardIndex<'a> {
+    keys: Vec<&'a str>,
+    reversed_keys: Vec<(String, &'a str)>,
+}
+
+impl<'a> WildcardIndex<'a> {
+    pub fn create(entries: &'a [DictionaryEntry]) -> WildcardIndex<'a> {
+        let keys: Vec<&'a str> = entries
+            .iter()
+            .flat_map(DictionaryEntry::headwords)
+            .filter(|key| !key.is_empty())
+            .sorted_unstable()
+            .dedup()
+            .collect();
+        let reversed_keys = keys
+            .iter()
+            .map(|key| (key.chars().rev().collect::<String>(), *key))
+            .sorted_unstable()
+            .collect();
+        WildcardIndex {
+            keys,
+            reversed_keys,
+        }
+    }
+
+    /// Keys matching `pattern`, narrowed down by its literal prefix or suffix
+    /// before the whole pattern is checked.
+    pub fn matching<'b>(
+        &'b self,
+        pattern: &'b WildcardPattern,
+    ) -> Box<dyn Iterator<Item = &'a str> + 'b> {
+        let candidates: Box<dyn Iterator<Item = &'a str> + 'b> =
+            match (pattern.prefix.as_str(), pattern.suffix.as_str()) {
+                ("", "") => Box::new(self.keys.iter().copied()),
+                ("", suffix) => {
+                    let reversed: String = suffix.chars().rev().collect();
+                    let start = self
+                        .reversed_keys
+                        .partition_point(|(key, _)| key.as_str() < reversed.as_str());
+                    Box::new(
+                        self.reversed_keys[start..]
+                            .iter()
+                            .take_while(move |(key, _)| key.starts_with(&reversed))
+                            .map(|(_, key)| *key),
+                    )
+                }
+                (prefix, _) => {
+                    let start = self.keys.partition_point(|key| *key < prefix);
+                    Box::new(
+                        self.keys[start..]
+                            .iter()
+                            .take_while(move |key| key.starts_with(prefix))
+                            .copied(),
+                    )
+                }
+            };
+        Box::new(candidates.filter(|key| pattern.is_match(key)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::word_entry;
+
+    #[test]
+    fn test_wildcard_pattern() {
+        let pattern = WildcardPattern::parse("食べ*").unwrap();
+        assert!(pattern.is_match("食べる") && pattern.is_match("食べ物"));
+        assert!(!pattern.is_match("お食べ"));
+        let pattern = WildcardPattern::parse("?き").unwrap();
+        assert!(pattern.is_match("好き") && !pattern.is_match("き"));
+        assert_eq!(pattern.suffix, "き");
+        assert!(WildcardPattern::parse("*的").unwrap().is_match("的"));
+        assert!(WildcardPattern::parse("食べる").is_none());
+        let patterns = WildcardPattern::parse_with_kana("tabe*");
+        assert_eq!(patterns.len(), 3);
+        assert!(patterns[1].is_match("たべもの") && patterns[2].is_match("タベル"));
+    }
+
+    #[test]
+    fn test_wildcard_index() {
+        let entries = vec![
+            word_entry("食べる", "たべる", &["v1"], None),
+            word_entry("食べ物", "たべもの", &["n"], None),
+            word_entry("飲み物", "のみもの", &["n"], None),
+            word_entry("物", "もの", &["n"], None),
+            word_entry("お食事", "おしょくじ", &["n"], None),
+        ];
+        let index = WildcardIndex::create(&entries);
+        let matching = |query: &str| -> Vec<&str> {
+            let pattern = WildcardPattern::parse(query).unwrap();
+            index.matching(&pattern).sorted().collect()
+        };
+        assert_eq!(matching("食べ*"), ["食べる", "食べ物"]);
+        assert_eq!(matching("*物"), ["物", "食べ物", "飲み物"]);
+        assert_eq!(matching("*もの"), ["たべもの", "のみもの", "もの"]);
+        assert_eq!(matching("食*物"), ["食べ物"]);
+        assert_eq!(matching("?食*"), ["お食事"]);
+        assert_eq!(matching("*").len(), 10);
+    }
+}